@@ -40,7 +40,7 @@ fn main() {
         })
         .expect("no cancelling listeners registered");
 
-    for line in std::io::stdin().lock().lines().flatten() {
+    for line in std::io::stdin().lock().lines().map_while(Result::ok) {
         match line.as_str() {
             "@exit" => {
                 println!("Exiting...");
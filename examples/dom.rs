@@ -59,18 +59,19 @@ fn main() {
     });
 
     // the `p` element will automatically update its text to match the text "entered" in the `input` element
+    // (only the text of the `input` element is tracked, so `p` isn't recomputed when e.g. its children change)
     let p = dom
         .create(move |dom, _| Element {
             parent: Some(body),
             text: dom
-                .read(input, |el| el.text.clone())
+                .read_projected(input, |el| el.text.clone())
                 .expect("no cancelling listeners registered")
                 .expect("`input` exists"),
             children: vec![],
         })
         .expect("no cancelling listeners registered");
 
-    for line in std::io::stdin().lock().lines().flatten() {
+    for line in std::io::stdin().lock().lines().map_while(Result::ok) {
         match line.as_str() {
             "exit" => {
                 println!("Exiting...");
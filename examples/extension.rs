@@ -111,7 +111,7 @@ fn main() {
         .create(|_, _| Item::default())
         .expect("no cancelling listeners registered");
 
-    for line in std::io::stdin().lock().lines().flatten() {
+    for line in std::io::stdin().lock().lines().map_while(Result::ok) {
         match line.as_str() {
             "@exit" => {
                 println!("Exiting...");
@@ -79,7 +79,7 @@ fn main() -> Result<(), Box<dyn Error>> {
         });
     });
 
-    for line in std::io::stdin().lock().lines().flatten() {
+    for line in std::io::stdin().lock().lines().map_while(Result::ok) {
         match line.as_str() {
             "@exit" => {
                 println!("Exiting...");
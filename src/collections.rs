@@ -0,0 +1,632 @@
+use crate::{compat::VariableBounds, error::Error, system::System, transaction::Transaction, variable::Variable, VariableId};
+
+use alloc::{
+    collections::{BTreeMap, BTreeSet},
+    vec::Vec,
+};
+use core::{iter, mem};
+
+/// An element has just been inserted into the targeted collection, at the given index or key.\
+/// Cancelling this event has no effect.
+#[derive(Debug, Clone)]
+pub struct Inserted<K> {
+    /// The index or key at which the element was inserted
+    pub key: K,
+}
+
+/// An element has just been removed from the targeted collection, from the given index or key.\
+/// Cancelling this event has no effect.
+#[derive(Debug, Clone)]
+pub struct Removed<K> {
+    /// The index or key from which the element was removed
+    pub key: K,
+}
+
+/// The element at the given index or key of the targeted collection has just been replaced.\
+/// Cancelling this event has no effect.
+#[derive(Debug, Clone)]
+pub struct Changed<K> {
+    /// The index or key of the element that was replaced
+    pub key: K,
+}
+
+/// An element of the targeted collection has just been moved from one index to another, shifting the elements in between.\
+/// Cancelling this event has no effect.
+#[derive(Debug, Clone)]
+pub struct Moved<K> {
+    /// The index that the element was moved from
+    pub from: K,
+    /// The index that the element was moved to
+    pub to: K,
+}
+
+/// A vector whose elements are each tracked individually by the reactive system.\
+/// Each index is backed by its own variable, so a recipe that reads a single element is only re-run when the element at
+/// that index changes, and a recipe that reads the length is only re-run when the length changes.\
+/// Changes to the vector emit the [`Inserted`], [`Removed`], [`Changed`] and [`Moved`] events on the vector itself.
+///
+/// # Example
+/// ```
+/// use korhah::{collections::Inserted, ReactiveVec};
+///
+/// let mut system = korhah::System::default();
+///
+/// let vec = ReactiveVec::new(&mut system).expect("no cancelling listeners registered");
+/// vec.push(&mut system, 1).expect("no cancelling listeners registered");
+///
+/// let first = system.create(move |s, _| {
+///     vec.get(s, 0, |v| *v).expect("no cancelling listeners registered")
+/// }).expect("no cancelling listeners registered");
+/// let len = system.create(move |s, _| {
+///     vec.len(s).expect("no cancelling listeners registered")
+/// }).expect("no cancelling listeners registered");
+///
+/// let inserted = system.create(|_, _| Vec::new()).expect("no cancelling listeners registered");
+/// system.listen(vec, move |s, e: &Inserted<usize>, _, _| {
+///     _ = s.update(inserted, |v| v.push(e.key));
+/// });
+///
+/// vec.push(&mut system, 2).expect("no cancelling listeners registered");
+/// vec.insert(&mut system, 0, 0).expect("no cancelling listeners registered");
+/// assert_eq!(Ok(Some(Some(0))), system.read(first, |v| *v));
+/// assert_eq!(Ok(Some(3)), system.read(len, |v| *v));
+/// assert_eq!(Ok(Some(vec![1, 0])), system.read(inserted, |v| v.clone()));
+/// assert_eq!(Ok(vec![0, 1, 2]), vec.to_vec(&system));
+/// ```
+#[derive(educe::Educe)]
+#[educe(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ReactiveVec<T: VariableBounds> {
+    /// the variable backing each index- there may be more of them than there are elements, in which case the excess ones are empty
+    slots: Variable<Vec<Variable<Option<T>>>>,
+    len: Variable<usize>,
+}
+
+impl<T: VariableBounds> From<ReactiveVec<T>> for Option<VariableId> {
+    fn from(vec: ReactiveVec<T>) -> Self {
+        vec.slots.into()
+    }
+}
+
+impl<T: VariableBounds> From<ReactiveVec<T>> for VariableId {
+    fn from(vec: ReactiveVec<T>) -> Self {
+        vec.slots.into()
+    }
+}
+
+impl<T: VariableBounds> ReactiveVec<T> {
+    /// Create a new, empty vector in the reactive system.
+    ///
+    /// Returns:
+    /// - [`Err`], if the action was cancelled (see [`Error`])
+    /// - an [`Ok`] value containing the new vector, otherwise
+    pub fn new(system: &mut System<'_>) -> Result<Self, Error> {
+        let slots = system.create(|_, _| Vec::new())?;
+        let len = system.create(|_, _| 0)?;
+        Ok(Self { slots, len })
+    }
+
+    /// Read the number of elements in the vector.
+    ///
+    /// Returns:
+    /// - [`Err`], if the action was cancelled (see [`Error`])
+    /// - an [`Ok`] value containing the number of elements, otherwise
+    pub fn len(&self, system: &System<'_>) -> Result<usize, Error> {
+        system
+            .read(self.len, |len| *len)
+            .map(Option::unwrap_or_default)
+    }
+
+    /// Read whether the vector has no elements.
+    ///
+    /// Returns:
+    /// - [`Err`], if the action was cancelled (see [`Error`])
+    /// - an [`Ok`] value containing whether the vector is empty, otherwise
+    pub fn is_empty(&self, system: &System<'_>) -> Result<bool, Error> {
+        self.len(system).map(|len| len == 0)
+    }
+
+    /// Read the element at the given index.\
+    /// Only the element at the given index is tracked, unless the index is out of bounds in which case the length is tracked.
+    ///
+    /// Returns:
+    /// - [`Err`], if the action was cancelled (see [`Error`])
+    /// - an [`Ok`] value containing [`None`], if the index is out of bounds
+    /// - an [`Ok`] value containing a [`Some`] value containing the result of the passed callback, otherwise
+    pub fn get<F, S>(&self, system: &System<'_>, index: usize, callback: F) -> Result<Option<S>, Error>
+    where
+        F: FnOnce(&T) -> S,
+    {
+        match self.slot(system, index)? {
+            // slots beyond the end of the vector are empty
+            Some(slot) => system
+                .read(slot, |value| value.as_ref().map(callback))
+                .map(Option::flatten),
+            None => {
+                // the index may come into bounds once the vector grows
+                system.read(self.len, |_| ())?;
+                Ok(None)
+            }
+        }
+    }
+
+    /// Read a copy of every element in the vector.
+    ///
+    /// Returns:
+    /// - [`Err`], if the action was cancelled (see [`Error`])
+    /// - an [`Ok`] value containing the elements, otherwise
+    pub fn to_vec(&self, system: &System<'_>) -> Result<Vec<T>, Error>
+    where
+        T: Clone,
+    {
+        let len = self.len(system)?;
+        let mut values = Vec::with_capacity(len);
+        for index in 0..len {
+            if let Some(value) = self.get(system, index, T::clone)? {
+                values.push(value);
+            }
+        }
+        Ok(values)
+    }
+
+    /// Replace the element at the given index, emitting the [`Changed`] event.
+    ///
+    /// Returns:
+    /// - [`Err`], if the action was cancelled (see [`Error`])
+    /// - an [`Ok`] value containing [`None`], if the index is out of bounds
+    /// - an [`Ok`] value containing a [`Some`] value containing the replaced element, otherwise
+    pub fn set(&self, system: &mut System<'_>, index: usize, value: T) -> Result<Option<T>, Error> {
+        if index >= self.count(system)? {
+            return Ok(None);
+        }
+        let slot = match self.slot(system, index)? {
+            Some(slot) => slot,
+            None => return Ok(None),
+        };
+
+        let prev = system
+            .update(slot, |slot| slot.replace(value))?
+            .flatten();
+        // we don't care if the `Changed` event is cancelled as there are no subsequent actions to take
+        _ = system.emit(*self, &Changed { key: index });
+        Ok(prev)
+    }
+
+    /// Append an element to the end of the vector, emitting the [`Inserted`] event.
+    ///
+    /// Returns:
+    /// - [`Err`], if the action was cancelled (see [`Error`])
+    /// - an [`Ok`] value, otherwise
+    pub fn push(&self, system: &mut System<'_>, value: T) -> Result<(), Error>
+    where
+        T: Clone,
+    {
+        let len = self.count(system)?;
+        self.insert(system, len, value).map(|_| ())
+    }
+
+    /// Insert an element at the given index, shifting the subsequent elements along and emitting the [`Inserted`] event.\
+    /// If any of the elements can't be shifted (eg: if the update of its index is cancelled), none of them are.
+    ///
+    /// Returns:
+    /// - [`Err`], if the action was cancelled (see [`Error`])
+    /// - an [`Ok`] value containing [`None`], if the index is out of bounds
+    /// - an [`Ok`] value containing [`Some`], otherwise
+    ///
+    /// # Example
+    /// ```
+    /// let mut system = korhah::System::default();
+    ///
+    /// let vec = korhah::ReactiveVec::new(&mut system).expect("no cancelling listeners registered");
+    /// for n in 0..4 {
+    ///     vec.push(&mut system, n).expect("no cancelling listeners registered");
+    /// }
+    ///
+    /// assert_eq!(Ok(Some(())), vec.insert(&mut system, 1, 10));
+    /// assert_eq!(Ok(vec![0, 10, 1, 2, 3]), vec.to_vec(&system));
+    ///
+    /// // out of bounds
+    /// assert_eq!(Ok(None), vec.insert(&mut system, 6, 20));
+    /// assert_eq!(Ok(Some(())), vec.insert(&mut system, 5, 20));
+    /// assert_eq!(Ok(vec![0, 10, 1, 2, 3, 20]), vec.to_vec(&system));
+    /// ```
+    pub fn insert(&self, system: &mut System<'_>, index: usize, value: T) -> Result<Option<()>, Error>
+    where
+        T: Clone,
+    {
+        let len = self.count(system)?;
+        if index > len {
+            return Ok(None);
+        }
+
+        // every affected element is shifted before any of the updates are propagated, and all of them are undone if any of
+        // them fail
+        let mut created = None;
+        let ret = system.transaction(|tx| {
+            // the vector may need another slot to accommodate its new last element
+            let slots = tx.peek(self.slots, |slots| slots.len())?.unwrap_or_default();
+            if slots <= len {
+                let slot = tx.create_hidden(|_, _| None)?;
+                created = Some(slot);
+                tx.update(self.slots, |slots| slots.push(slot))?;
+            }
+
+            self.shift(tx, index..=len, Some(value))?;
+            tx.update(self.len, |len| *len += 1)?;
+            Ok(())
+        });
+        if let Err(err) = ret {
+            // the transaction only undoes updates, so any slot it created is discarded separately
+            if let Some(slot) = created {
+                _ = system.delete(slot);
+            }
+            return Err(err);
+        }
+
+        // we don't care if the `Inserted` event is cancelled as there are no subsequent actions to take
+        _ = system.emit(*self, &Inserted { key: index });
+        Ok(Some(()))
+    }
+
+    /// Remove the last element of the vector, emitting the [`Removed`] event.
+    ///
+    /// Returns:
+    /// - [`Err`], if the action was cancelled (see [`Error`])
+    /// - an [`Ok`] value containing [`None`], if the vector is empty
+    /// - an [`Ok`] value containing a [`Some`] value containing the removed element, otherwise
+    pub fn pop(&self, system: &mut System<'_>) -> Result<Option<T>, Error>
+    where
+        T: Clone,
+    {
+        match self.count(system)? {
+            0 => Ok(None),
+            len => self.remove(system, len - 1),
+        }
+    }
+
+    /// Remove the element at the given index, shifting the subsequent elements back and emitting the [`Removed`] event.\
+    /// If any of the elements can't be shifted (eg: if the update of its index is cancelled), none of them are.
+    ///
+    /// Returns:
+    /// - [`Err`], if the action was cancelled (see [`Error`])
+    /// - an [`Ok`] value containing [`None`], if the index is out of bounds
+    /// - an [`Ok`] value containing a [`Some`] value containing the removed element, otherwise
+    pub fn remove(&self, system: &mut System<'_>, index: usize) -> Result<Option<T>, Error>
+    where
+        T: Clone,
+    {
+        let len = self.count(system)?;
+        if index >= len {
+            return Ok(None);
+        }
+
+        // every affected element is shifted before any of the updates are propagated, and all of them are undone if any of
+        // them fail
+        let value = system.transaction(|tx| {
+            let value = self.shift(tx, (index..len).rev(), None)?;
+            tx.update(self.len, |len| *len -= 1)?;
+            Ok(value)
+        })?;
+
+        // we don't care if the `Removed` event is cancelled as there are no subsequent actions to take
+        _ = system.emit(*self, &Removed { key: index });
+        Ok(value)
+    }
+
+    /// Move the element at the given index to another index, shifting the elements in between and emitting the [`Moved`] event.\
+    /// If any of the elements can't be shifted (eg: if the update of its index is cancelled), none of them are.
+    ///
+    /// Returns:
+    /// - [`Err`], if the action was cancelled (see [`Error`])
+    /// - an [`Ok`] value containing [`None`], if either index is out of bounds
+    /// - an [`Ok`] value containing [`Some`], otherwise
+    ///
+    /// # Example
+    /// ```
+    /// let mut system = korhah::System::default();
+    ///
+    /// let vec = korhah::ReactiveVec::new(&mut system).expect("no cancelling listeners registered");
+    /// for n in 0..5 {
+    ///     vec.push(&mut system, n).expect("no cancelling listeners registered");
+    /// }
+    ///
+    /// assert_eq!(Ok(Some(())), vec.move_item(&mut system, 1, 3));
+    /// assert_eq!(Ok(vec![0, 2, 3, 1, 4]), vec.to_vec(&system));
+    ///
+    /// assert_eq!(Ok(Some(())), vec.move_item(&mut system, 4, 0));
+    /// assert_eq!(Ok(vec![4, 0, 2, 3, 1]), vec.to_vec(&system));
+    ///
+    /// assert_eq!(Ok(None), vec.move_item(&mut system, 0, 5));
+    /// ```
+    pub fn move_item(&self, system: &mut System<'_>, from: usize, to: usize) -> Result<Option<()>, Error>
+    where
+        T: Clone,
+    {
+        let len = self.count(system)?;
+        if from >= len || to >= len {
+            return Ok(None);
+        }
+
+        // the element is taken out, the elements in between are shifted towards the vacated index, and the element is put
+        // back in at the vacated target index- all of which is undone if any of it fails
+        system.transaction(|tx| {
+            let value = if from < to {
+                self.shift(tx, (from..=to).rev(), None)?
+            } else {
+                self.shift(tx, to..=from, None)?
+            };
+            self.shift(tx, iter::once(to), value)?;
+            Ok(())
+        })?;
+
+        // we don't care if the `Moved` event is cancelled as there are no subsequent actions to take
+        _ = system.emit(*self, &Moved { from, to });
+        Ok(Some(()))
+    }
+
+    /// the number of elements in the vector, without tracking it
+    fn count(&self, system: &System<'_>) -> Result<usize, Error> {
+        system
+            .peek(self.len, |len| *len)
+            .map(Option::unwrap_or_default)
+    }
+
+    /// the variable backing the given index, if any, without tracking the slots
+    fn slot(&self, system: &System<'_>, index: usize) -> Result<Option<Variable<Option<T>>>, Error> {
+        system
+            .peek(self.slots, |slots| slots.get(index).copied())
+            .map(Option::flatten)
+    }
+
+    /// pass a value through the slots at the given indices in turn, with each slot taking the value carried over from the
+    /// previous one, returning the value carried over from the last one- the updates are recorded by the given transaction,
+    /// so that they can be undone
+    fn shift(
+        &self,
+        tx: &mut Transaction<'_, '_>,
+        indices: impl Iterator<Item = usize>,
+        mut carry: Option<T>,
+    ) -> Result<Option<T>, Error>
+    where
+        T: Clone,
+    {
+        for index in indices {
+            if let Some(slot) = self.slot(tx, index)? {
+                carry = tx
+                    .update(slot, |slot| mem::replace(slot, carry))?
+                    .flatten();
+            }
+        }
+        Ok(carry)
+    }
+}
+
+/// An ordered map whose entries are each tracked individually by the reactive system.\
+/// Each key is backed by its own variable, so a recipe that reads a single key is only re-run when the entry for that key
+/// changes, and a recipe that reads the length is only re-run when the length changes.\
+/// Changes to the map emit the [`Inserted`], [`Removed`] and [`Changed`] events on the map itself.
+///
+/// # Example
+/// ```
+/// use std::sync::atomic::{AtomicUsize, Ordering};
+///
+/// static RUNS: AtomicUsize = AtomicUsize::new(0);
+///
+/// let mut system = korhah::System::default();
+///
+/// let map = korhah::ReactiveMap::new(&mut system).expect("no cancelling listeners registered");
+/// map.insert(&mut system, "a", 1).expect("no cancelling listeners registered");
+///
+/// let a = system.create(move |s, _| {
+///     RUNS.fetch_add(1, Ordering::Relaxed);
+///     map.get(s, &"a", |v| *v).expect("no cancelling listeners registered")
+/// }).expect("no cancelling listeners registered");
+///
+/// // `a` only depends on the entry for "a"
+/// map.insert(&mut system, "b", 2).expect("no cancelling listeners registered");
+/// assert_eq!(1, RUNS.load(Ordering::Relaxed));
+///
+/// map.insert(&mut system, "a", 3).expect("no cancelling listeners registered");
+/// assert_eq!(Ok(Some(Some(3))), system.read(a, |v| *v));
+///
+/// assert_eq!(Ok(Some(3)), map.remove(&mut system, &"a"));
+/// assert_eq!(Ok(Some(None)), system.read(a, |v| *v));
+/// assert_eq!(Ok(vec!["b"]), map.keys(&system));
+/// ```
+#[derive(educe::Educe)]
+#[educe(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ReactiveMap<K: VariableBounds + Ord + Clone, V: VariableBounds> {
+    /// the variable backing each key- keys that have been removed may still be backed by an empty variable, if any recipes
+    /// depend on them
+    slots: Variable<BTreeMap<K, Variable<Option<V>>>>,
+    keys: Variable<BTreeSet<K>>,
+    len: Variable<usize>,
+}
+
+impl<K: VariableBounds + Ord + Clone, V: VariableBounds> From<ReactiveMap<K, V>> for Option<VariableId> {
+    fn from(map: ReactiveMap<K, V>) -> Self {
+        map.slots.into()
+    }
+}
+
+impl<K: VariableBounds + Ord + Clone, V: VariableBounds> From<ReactiveMap<K, V>> for VariableId {
+    fn from(map: ReactiveMap<K, V>) -> Self {
+        map.slots.into()
+    }
+}
+
+impl<K: VariableBounds + Ord + Clone, V: VariableBounds> ReactiveMap<K, V> {
+    /// Create a new, empty map in the reactive system.
+    ///
+    /// Returns:
+    /// - [`Err`], if the action was cancelled (see [`Error`])
+    /// - an [`Ok`] value containing the new map, otherwise
+    pub fn new(system: &mut System<'_>) -> Result<Self, Error> {
+        let slots = system.create(|_, _| BTreeMap::new())?;
+        let keys = system.create(|_, _| BTreeSet::new())?;
+        // the length is only recomputed when the set of keys changes, but it only propagates when the number of keys changes
+        let len = system.create_eq(move |s, _| {
+            s.read(keys, |keys: &BTreeSet<K>| keys.len())
+                .ok()
+                .flatten()
+                .unwrap_or_default()
+        })?;
+        Ok(Self { slots, keys, len })
+    }
+
+    /// Read the number of entries in the map.
+    ///
+    /// Returns:
+    /// - [`Err`], if the action was cancelled (see [`Error`])
+    /// - an [`Ok`] value containing the number of entries, otherwise
+    pub fn len(&self, system: &System<'_>) -> Result<usize, Error> {
+        system
+            .read(self.len, |len| *len)
+            .map(Option::unwrap_or_default)
+    }
+
+    /// Read whether the map has no entries.
+    ///
+    /// Returns:
+    /// - [`Err`], if the action was cancelled (see [`Error`])
+    /// - an [`Ok`] value containing whether the map is empty, otherwise
+    pub fn is_empty(&self, system: &System<'_>) -> Result<bool, Error> {
+        self.len(system).map(|len| len == 0)
+    }
+
+    /// Read a copy of every key in the map, in order.
+    ///
+    /// Returns:
+    /// - [`Err`], if the action was cancelled (see [`Error`])
+    /// - an [`Ok`] value containing the keys, otherwise
+    pub fn keys(&self, system: &System<'_>) -> Result<Vec<K>, Error> {
+        system
+            .read(self.keys, |keys| keys.iter().cloned().collect())
+            .map(Option::unwrap_or_default)
+    }
+
+    /// Read the value of the entry for the given key.\
+    /// Only the entry for the given key is tracked, unless the key has never been present in which case the set of keys is tracked.
+    ///
+    /// Returns:
+    /// - [`Err`], if the action was cancelled (see [`Error`])
+    /// - an [`Ok`] value containing [`None`], if the map has no entry for the given key
+    /// - an [`Ok`] value containing a [`Some`] value containing the result of the passed callback, otherwise
+    pub fn get<F, S>(&self, system: &System<'_>, key: &K, callback: F) -> Result<Option<S>, Error>
+    where
+        F: FnOnce(&V) -> S,
+    {
+        match self.slot(system, key)? {
+            // the slots of removed keys are empty
+            Some(slot) => system
+                .read(slot, |value| value.as_ref().map(callback))
+                .map(Option::flatten),
+            None => {
+                // the key may be inserted later on
+                system.read(self.keys, |_| ())?;
+                Ok(None)
+            }
+        }
+    }
+
+    /// Read whether the map has an entry for the given key, tracking it just like [`ReactiveMap::get`].
+    ///
+    /// Returns:
+    /// - [`Err`], if the action was cancelled (see [`Error`])
+    /// - an [`Ok`] value containing whether the map has an entry for the given key, otherwise
+    pub fn contains_key(&self, system: &System<'_>, key: &K) -> Result<bool, Error> {
+        self.get(system, key, |_| ()).map(|entry| entry.is_some())
+    }
+
+    /// Insert an entry into the map, emitting the [`Inserted`] event if the key is new or the [`Changed`] event otherwise.
+    ///
+    /// Returns:
+    /// - [`Err`], if the action was cancelled (see [`Error`])
+    /// - an [`Ok`] value containing the previous value of the entry, if any
+    pub fn insert(&self, system: &mut System<'_>, key: K, value: V) -> Result<Option<V>, Error>
+    where
+        V: Clone,
+    {
+        // every affected variable is updated before any of the updates are propagated, and all of them are undone if any
+        // of them fail
+        let mut created = None;
+        let ret = system.transaction(|tx| {
+            let slot = match self.slot(tx, &key)? {
+                Some(slot) => slot,
+                None => {
+                    let slot = tx.create_hidden(|_, _| None)?;
+                    created = Some(slot);
+                    tx.update(self.slots, |slots| slots.insert(key.clone(), slot))?;
+                    slot
+                }
+            };
+
+            let prev = tx.update(slot, |slot| slot.replace(value))?.flatten();
+            if prev.is_none() {
+                tx.update(self.keys, |keys| keys.insert(key.clone()))?;
+            }
+            Ok(prev)
+        });
+        let prev = match ret {
+            Ok(prev) => prev,
+            Err(err) => {
+                // the transaction only undoes updates, so any slot it created is discarded separately
+                if let Some(slot) = created {
+                    _ = system.delete(slot);
+                }
+                return Err(err);
+            }
+        };
+
+        // we don't care if the `Inserted`/`Changed` events are cancelled as there are no subsequent actions to take
+        if prev.is_none() {
+            _ = system.emit(*self, &Inserted { key });
+        } else {
+            _ = system.emit(*self, &Changed { key });
+        }
+        Ok(prev)
+    }
+
+    /// Remove the entry for the given key from the map, emitting the [`Removed`] event.
+    ///
+    /// Returns:
+    /// - [`Err`], if the action was cancelled (see [`Error`])
+    /// - an [`Ok`] value containing the removed value, if any
+    pub fn remove(&self, system: &mut System<'_>, key: &K) -> Result<Option<V>, Error>
+    where
+        V: Clone,
+    {
+        let slot = match self.slot(system, key)? {
+            Some(slot) => slot,
+            None => return Ok(None),
+        };
+
+        // every affected variable is updated before any of the updates are propagated, and all of them are undone if any
+        // of them fail
+        let prev = system.transaction(|tx| {
+            let prev = tx.update(slot, Option::take)?.flatten();
+            if prev.is_some() {
+                tx.update(self.keys, |keys| keys.remove(key))?;
+            }
+            Ok(prev)
+        })?;
+
+        if prev.is_some() {
+            // the slot is kept around as long as any recipes still depend on it, so that they're re-run if the key is
+            // inserted again
+            if let Ok(Some(_)) = system.delete(slot) {
+                system.update(self.slots, |slots| slots.remove(key))?;
+            }
+
+            // we don't care if the `Removed` event is cancelled as there are no subsequent actions to take
+            _ = system.emit(*self, &Removed { key: key.clone() });
+        }
+        Ok(prev)
+    }
+
+    /// the variable backing the given key, if any, without tracking the slots
+    fn slot(&self, system: &System<'_>, key: &K) -> Result<Option<Variable<Option<V>>>, Error> {
+        system
+            .peek(self.slots, |slots| slots.get(key).copied())
+            .map(Option::flatten)
+    }
+}
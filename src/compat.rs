@@ -1,14 +1,18 @@
-pub use compat::*;
+#[cfg(not(feature = "unsync"))]
+pub use sync::*;
+#[cfg(feature = "unsync")]
+pub use unsync::*;
 
 #[cfg(not(feature = "unsync"))]
-mod compat {
+mod sync {
     use alloc::boxed::Box;
-    use core::any::Any;
+    use core::{any::Any, future::Future, pin::Pin};
 
     pub(crate) use ::spin::{Mutex as Cell, MutexGuard as Guard};
+    pub(crate) use ::spin::{RwLock as Shared, RwLockReadGuard as SharedRef, RwLockWriteGuard as SharedMut};
     pub(crate) use alloc::sync::Arc as Rc;
 
-    use crate::{listener::Vote, system::System};
+    use crate::{error::Error, listener::Vote, system::System};
 
     pub trait VariableBounds: Any + Send + Sync {}
     impl<T: Any + Send + Sync> VariableBounds for T {}
@@ -16,22 +20,73 @@ mod compat {
     pub trait FnBounds: Send + Sync {}
     impl<F: Send + Sync> FnBounds for F {}
 
+    pub trait FutureBounds: Send {}
+    impl<F: Send> FutureBounds for F {}
+
+    pub trait EventBounds: Any + Send {}
+    impl<E: Any + Send> EventBounds for E {}
+
+    pub(crate) fn lock<T>(cell: &Cell<T>) -> Guard<'_, T> {
+        cell.lock()
+    }
+
+    pub(crate) fn read<T>(shared: &Shared<T>) -> SharedRef<'_, T> {
+        shared.read()
+    }
+
+    pub(crate) fn write<T>(shared: &Shared<T>) -> SharedMut<'_, T> {
+        shared.write()
+    }
+
+    /// like `write`, except that new readers are held off while waiting for the current ones to finish, so that a steady
+    /// stream of readers can't starve the writer
+    pub(crate) fn claim<T>(shared: &Shared<T>) -> SharedMut<'_, T> {
+        shared.upgradeable_read().upgrade()
+    }
+
+    pub(crate) fn try_read<T>(shared: &Shared<T>) -> Option<SharedRef<'_, T>> {
+        shared.try_read()
+    }
+
+    /// identifies the thread acting on the system- without `std` threads can't be told apart, so they're all treated as one
+    #[cfg(feature = "std")]
+    pub(crate) type ThreadId = std::thread::ThreadId;
+    #[cfg(not(feature = "std"))]
+    #[derive(Clone, Copy, PartialEq, Eq)]
+    pub(crate) struct ThreadId;
+
+    #[cfg(feature = "std")]
+    pub(crate) fn current_thread() -> ThreadId {
+        std::thread::current().id()
+    }
+
+    #[cfg(not(feature = "std"))]
+    pub(crate) fn current_thread() -> ThreadId {
+        ThreadId
+    }
+
     pub(crate) type Value = Box<dyn Any + Send + Sync>;
+    pub(crate) type Message = Box<dyn Any + Send>;
+    pub(crate) type BoxFuture<'a> = Pin<Box<dyn Future<Output = ()> + Send + 'a>>;
 
     pub(crate) type Handler<'a> =
         Rc<dyn Fn(&mut System<'a>, &dyn Any, &mut Vote, &mut bool) + Send + Sync + 'a>;
-    pub(crate) type Recipe<'a> = Rc<dyn Fn(&System<'a>) -> Value + Send + Sync + 'a>;
+    pub(crate) type Recipe<'a> = Rc<dyn Fn(&System<'a>, Option<Value>) -> (Value, bool) + Send + Sync + 'a>;
+    pub(crate) type Comparator<'a> = Rc<dyn Fn(&Value, &Value) -> bool + Send + Sync + 'a>;
+    pub(crate) type Action<'a> = Rc<dyn Fn(&mut System<'a>) + Send + Sync + 'a>;
+    pub(crate) type Setter<'a> = Rc<dyn Fn(&mut System<'a>, Value) -> Result<(), Error> + Send + Sync + 'a>;
 }
 
 #[cfg(feature = "unsync")]
-mod compat {
+mod unsync {
     use alloc::boxed::Box;
-    use core::any::Any;
+    use core::{any::Any, future::Future, pin::Pin};
 
     pub(crate) use alloc::rc::Rc;
     pub(crate) use core::cell::{RefCell as Cell, RefMut as Guard};
+    pub(crate) use core::cell::{Ref as SharedRef, RefCell as Shared, RefMut as SharedMut};
 
-    use crate::{listener::Vote, system::System};
+    use crate::{error::Error, listener::Vote, system::System};
 
     pub trait VariableBounds: Any {}
     impl<T: Any> VariableBounds for T {}
@@ -39,8 +94,47 @@ mod compat {
     pub trait FnBounds {}
     impl<F> FnBounds for F {}
 
+    pub trait FutureBounds {}
+    impl<F> FutureBounds for F {}
+
+    pub trait EventBounds: Any {}
+    impl<E: Any> EventBounds for E {}
+
+    pub(crate) fn lock<T>(cell: &Cell<T>) -> Guard<'_, T> {
+        cell.borrow_mut()
+    }
+
+    pub(crate) fn read<T>(shared: &Shared<T>) -> SharedRef<'_, T> {
+        shared.borrow()
+    }
+
+    pub(crate) fn write<T>(shared: &Shared<T>) -> SharedMut<'_, T> {
+        shared.borrow_mut()
+    }
+
+    pub(crate) fn claim<T>(shared: &Shared<T>) -> SharedMut<'_, T> {
+        shared.borrow_mut()
+    }
+
+    pub(crate) fn try_read<T>(shared: &Shared<T>) -> Option<SharedRef<'_, T>> {
+        shared.try_borrow().ok()
+    }
+
+    /// the system can't be shared between threads, so only one thread ever acts on it
+    #[derive(Clone, Copy, PartialEq, Eq)]
+    pub(crate) struct ThreadId;
+
+    pub(crate) fn current_thread() -> ThreadId {
+        ThreadId
+    }
+
     pub(crate) type Value = Box<dyn Any>;
+    pub(crate) type Message = Box<dyn Any>;
+    pub(crate) type BoxFuture<'a> = Pin<Box<dyn Future<Output = ()> + 'a>>;
 
     pub(crate) type Handler<'a> = Rc<dyn Fn(&mut System<'a>, &dyn Any, &mut Vote, &mut bool) + 'a>;
-    pub(crate) type Recipe<'a> = Rc<dyn Fn(&System<'a>) -> Value + 'a>;
+    pub(crate) type Recipe<'a> = Rc<dyn Fn(&System<'a>, Option<Value>) -> (Value, bool) + 'a>;
+    pub(crate) type Comparator<'a> = Rc<dyn Fn(&Value, &Value) -> bool + 'a>;
+    pub(crate) type Action<'a> = Rc<dyn Fn(&mut System<'a>) + 'a>;
+    pub(crate) type Setter<'a> = Rc<dyn Fn(&mut System<'a>, Value) -> Result<(), Error> + 'a>;
 }
@@ -0,0 +1,5 @@
+use crate::Id;
+
+/// A handle to an effect in the reactive system
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Effect(pub(crate) Id);
@@ -0,0 +1,50 @@
+use crate::{
+    listener::{ListenerId, Votes},
+    variable::VariableId,
+};
+
+use alloc::vec::Vec;
+use core::fmt;
+
+/// The reasons an action in the reactive system can fail
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Error {
+    /// The action was cancelled by the handlers of its corresponding built-in event, as the votes to cancel outnumbered the
+    /// votes to proceed
+    Cancelled(Votes),
+    /// The action, or the event it triggered, was aborted by the given listener
+    Aborted(ListenerId),
+    /// The target variable couldn't be deleted, since the given variables depend on it and would otherwise be left with a
+    /// dangling reference
+    HasDependents(Vec<VariableId>),
+    /// The action would have made a variable (transitively) depend on itself, or would have re-entered the propagation of
+    /// an update that is still in progress.\
+    /// The given variables form a chain of dependencies, ie: each variable is depended on by the next one.
+    Cycle(Vec<VariableId>),
+    /// The target variable doesn't hold a value of the expected type
+    TypeMismatch,
+    /// The target variable is already in the middle of being recomputed, or its value is already being accessed (eg: by
+    /// the callback of an enclosing read or update), on the same thread- so it can't be acted on until that has finished.\
+    /// Recomputations and accesses on other threads are waited on instead.
+    Reentrancy,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Cancelled(votes) => write!(
+                f,
+                "cancelled by {} vote(s) to {} ({} abstaining)",
+                votes.cancel, votes.proceed, votes.abstain
+            ),
+            Self::Aborted(listener) => write!(f, "aborted by {listener:?}"),
+            Self::HasDependents(dependents) => write!(f, "depended on by {dependents:?}"),
+            Self::Cycle(cycle) => write!(f, "dependency cycle formed by {cycle:?}"),
+            Self::TypeMismatch => write!(f, "type mismatch"),
+            Self::Reentrancy => write!(f, "re-entrant access"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Error {}
@@ -55,3 +55,28 @@ pub struct Deleted<T: VariableBounds> {
     /// The variable that was just deleted- it should not be used for any interactions with the system
     pub _source: Variable<T>,
 }
+
+/// The targeted variable's recipe is about to be replaced.\
+/// If this event is cancelled, the variable's recipe will not be replaced.
+#[derive(Clone, Copy)]
+pub struct Rewiring;
+
+/// The targeted variable's recipe has just been replaced, and the variable has been recomputed accordingly.\
+/// Cancelling this event has no effect.
+#[derive(Clone, Copy)]
+pub struct Rewired;
+
+/// The targeted variable is about to be detached from the variables it depends on, ie: it's about to become a plain variable.\
+/// If this event is cancelled, the variable will not be detached.
+#[derive(Clone, Copy)]
+pub struct Detaching;
+
+/// The targeted variable has just been detached from the variables it depends on, and now simply holds its most recent value.\
+/// Cancelling this event has no effect.
+#[derive(Clone, Copy)]
+pub struct Detached;
+
+/// The targeted lazy variable has just been marked as out-of-date, to be recomputed the next time it's read.\
+/// This event is internal to the crate- it lets anything waiting on the variable's value know that it may have changed.
+#[derive(Clone, Copy)]
+pub(crate) struct Dirtied;
@@ -0,0 +1,54 @@
+use alloc::vec::Vec;
+
+/// A generational index- indices are recycled once whatever they identify has been removed from the system, and the
+/// generation distinguishes each successive reuse of an index so that stale handles can never alias newer ones
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub(crate) struct Id {
+    index: u64,
+    generation: u64,
+}
+
+impl Id {
+    /// the first identifier for the given index
+    pub(crate) fn new(index: u64) -> Self {
+        Self {
+            index,
+            generation: 0,
+        }
+    }
+
+    /// the identifier for the next reuse of this identifier's index
+    pub(crate) fn next(self) -> Self {
+        Self {
+            index: self.index,
+            generation: self.generation.wrapping_add(1),
+        }
+    }
+}
+
+/// Allocates IDs, recycling those that have been freed
+#[derive(Default)]
+pub(crate) struct Allocator {
+    next_index: u64,
+    /// IDs that are free to be reused, each already advanced to its next generation
+    pool: Vec<Id>,
+}
+
+impl Allocator {
+    /// allocate a new ID
+    pub(crate) fn allocate(&mut self) -> Id {
+        // previously-freed IDs are reused if possible, otherwise new IDs are allocated by incrementing a counter
+        self.pool.pop().unwrap_or_else(|| {
+            let id = Id::new(self.next_index);
+            self.next_index += 1;
+            id
+        })
+    }
+
+    /// make the given ID available for reuse
+    pub(crate) fn free(&mut self, id: Id) {
+        // the reused ID belongs to a new generation, so that any outstanding handles holding the freed ID can't alias
+        // whatever it's reused for
+        self.pool.push(id.next());
+    }
+}
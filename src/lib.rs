@@ -2,16 +2,46 @@
 #![doc = include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/README.md"))]
 
 extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
 
+/// Collections whose elements are tracked individually by the reactive system
+pub mod collections;
 mod compat;
+mod effect;
+mod error;
 /// Events that are emitted as a result of built-in CRUD actions
 pub mod events;
+mod id;
 mod listener;
+mod next;
+mod policy;
+#[cfg(feature = "std")]
+mod sender;
+#[cfg(feature = "futures-core")]
+mod stream;
 mod system;
+mod task;
+#[cfg(all(test, feature = "std"))]
+mod tests;
+mod transaction;
 mod variable;
+#[cfg(all(feature = "std", not(feature = "unsync")))]
+mod wait;
 
-pub(crate) type Id = u128;
+pub(crate) use id::Id;
 
-pub use listener::{Listener, Vote, Votes};
+pub use collections::{ReactiveMap, ReactiveVec};
+pub use effect::Effect;
+pub use error::Error;
+pub use listener::{Listener, ListenerId, Vote, Votes};
+pub use next::Next;
+pub use policy::Policy;
+#[cfg(feature = "std")]
+pub use sender::EventSender;
+#[cfg(feature = "futures-core")]
+pub use stream::{Lagged, Overflow, Subscription};
 pub use system::System;
-pub use variable::Variable;
+pub use task::{AsyncState, Task};
+pub use transaction::Transaction;
+pub use variable::{Variable, VariableId};
@@ -6,11 +6,21 @@ use core::marker::PhantomData;
 #[derive(educe::Educe)]
 #[educe(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Listener<E: 'static> {
-    pub(crate) id: Id,
+    pub(crate) id: ListenerId,
     pub(crate) target: Option<Id>,
     pub(crate) _e: PhantomData<E>,
 }
 
+impl<E: 'static> From<Listener<E>> for ListenerId {
+    fn from(listener: Listener<E>) -> Self {
+        listener.id
+    }
+}
+
+/// An untyped handle to a listener in the reactive system
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ListenerId(pub(crate) Id);
+
 /// Represents a certain event handler's preference as to whether or not the effects from its corresponding event should be followed through
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum Vote {
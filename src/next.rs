@@ -0,0 +1,101 @@
+use crate::{
+    compat::{lock, Cell, Rc, VariableBounds},
+    listener::Listener,
+    system::System,
+    variable::VariableId,
+};
+
+use core::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll, Waker},
+};
+
+/// A future that resolves with the next occurrence of a certain event in the reactive system.\
+/// It resolves to [`None`] if its target variable doesn't exist (or is deleted before the event occurs).\
+/// The listener backing it is removed as soon as the event occurs, or as soon as it's dropped.
+pub struct Next<'x, E: VariableBounds + Clone> {
+    system: System<'x>,
+    slot: Rc<Cell<Slot<E>>>,
+}
+
+/// the state shared between the future and the listener backing it
+struct Slot<E: 'static> {
+    event: Option<E>,
+    /// whether the listener backing the future has been removed
+    closed: bool,
+    waker: Option<Waker>,
+    listener: Option<Listener<E>>,
+}
+
+/// held by the listener backing the future, so that the future is woken whenever the listener is removed
+struct Sender<E: 'static>(Rc<Cell<Slot<E>>>);
+
+impl<E: 'static> Drop for Sender<E> {
+    fn drop(&mut self) {
+        let waker = {
+            let mut slot = lock(&self.0);
+            slot.closed = true;
+            slot.waker.take()
+        };
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+    }
+}
+
+impl<'x, E: VariableBounds + Clone> Next<'x, E> {
+    pub(crate) fn new(system: System<'x>, target: Option<VariableId>) -> Self {
+        let slot = Rc::new(Cell::new(Slot {
+            event: None,
+            closed: false,
+            waker: None,
+            listener: None,
+        }));
+
+        let sender = Sender(slot.clone());
+        let listener = system.listen(target, move |s, e: &E, _, _| {
+            let listener = {
+                let mut slot = lock(&sender.0);
+                if slot.event.is_none() {
+                    slot.event = Some(e.clone());
+                }
+                slot.listener.take()
+            };
+            // only the first occurrence of the event is of interest- removing the listener wakes the future
+            if let Some(listener) = listener {
+                s.silence(listener);
+            }
+        });
+
+        // if the target variable doesn't exist, the listener (and so the sender) has already been dropped
+        lock(&slot).listener = listener;
+
+        Self { system, slot }
+    }
+}
+
+impl<E: VariableBounds + Clone> Future for Next<'_, E> {
+    type Output = Option<E>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut slot = lock(&self.slot);
+        if let Some(event) = slot.event.take() {
+            Poll::Ready(Some(event))
+        } else if slot.closed {
+            Poll::Ready(None)
+        } else {
+            slot.waker = Some(cx.waker().clone());
+            Poll::Pending
+        }
+    }
+}
+
+impl<E: VariableBounds + Clone> Drop for Next<'_, E> {
+    fn drop(&mut self) {
+        let listener = lock(&self.slot).listener.take();
+        if let Some(listener) = listener {
+            self.system.silence(listener);
+        }
+    }
+}
@@ -0,0 +1,11 @@
+/// Determines what happens to the variables that depend on a variable being deleted
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Policy {
+    /// Refuse to delete a variable that any other variables depend on
+    #[default]
+    Refuse,
+    /// Delete every variable that (transitively) depends on the deleted variable along with it
+    Cascade,
+    /// Freeze the variables that depend on the deleted variable, turning them into plain variables that hold their most recent value
+    Orphan,
+}
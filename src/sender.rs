@@ -0,0 +1,44 @@
+use crate::{variable::VariableId, Id};
+
+use alloc::boxed::Box;
+use core::any::Any;
+use std::sync::mpsc;
+
+/// an event sent from another thread, along with the ID of its target (if any)
+pub(crate) type Mail = (Option<Id>, Box<dyn Any + Send>);
+
+/// the receiving end of the events sent to a system, created the first time a sender is requested
+pub(crate) struct Inbox {
+    pub(crate) sender: mpsc::Sender<Mail>,
+    pub(crate) receiver: mpsc::Receiver<Mail>,
+}
+
+impl Default for Inbox {
+    fn default() -> Self {
+        let (sender, receiver) = mpsc::channel();
+        Self { sender, receiver }
+    }
+}
+
+/// A handle for queueing events in a reactive system from any thread, even if the system itself can't be shared between
+/// threads.\
+/// Sent events are queued just like posted events, and are triggered whenever the owning thread drains the system's queue
+/// (see [`System::post`](crate::System::post)).
+#[derive(Clone)]
+pub struct EventSender(pub(crate) mpsc::Sender<Mail>);
+
+impl EventSender {
+    /// Queue the given event to be triggered in the reactive system this sender belongs to.\
+    /// A [`None`] target queues an event in the global scope, whereas a [`Some`] target queues an event on that specific variable.
+    ///
+    /// Returns:
+    /// - [`None`], if the system no longer exists
+    /// - [`Some`], otherwise
+    pub fn send<E>(&self, target: impl Into<Option<VariableId>>, event: E) -> Option<()>
+    where
+        E: Any + Send,
+    {
+        let target_id = target.into().map(|VariableId(id)| id);
+        self.0.send((target_id, Box::new(event))).ok()
+    }
+}
@@ -0,0 +1,161 @@
+use crate::{
+    compat::{lock, Cell, FnBounds, Rc, VariableBounds},
+    listener::Listener,
+    system::System,
+    variable::VariableId,
+};
+
+use alloc::collections::VecDeque;
+use core::{
+    pin::Pin,
+    task::{Context, Poll, Waker},
+};
+
+use futures_core::Stream;
+
+/// What a [`Subscription`] does with a new item once its buffer is full.\
+/// Either way, the item is never waited on- emitting events is never blocked by a slow consumer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub enum Overflow {
+    /// The oldest buffered item is discarded to make room for the new item
+    #[default]
+    DropOldest,
+    /// The new item is discarded
+    DropNewest,
+}
+
+/// The consumer of a [`Subscription`] fell behind, and the given number of items were discarded since they were last
+/// notified (see [`Overflow`]).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Lagged(pub usize);
+
+/// A stream of items derived from each occurrence of a certain event in the reactive system.\
+/// Items are buffered up to a fixed capacity until they're consumed, at which point the stream's [`Overflow`] policy
+/// applies- whenever items have been discarded, the stream yields a [`Lagged`] error before yielding any more items.\
+/// The stream ends if its target variable doesn't exist (or is deleted), once every buffered item has been yielded.\
+/// The listener backing it is removed as soon as it's dropped.
+pub struct Subscription<'x, E: 'static, T> {
+    system: System<'x>,
+    channel: Rc<Cell<Channel<E, T>>>,
+}
+
+/// the state shared between the stream and the listener backing it
+struct Channel<E: 'static, T> {
+    buffer: VecDeque<T>,
+    capacity: usize,
+    overflow: Overflow,
+    /// the number of items discarded since the consumer was last notified
+    lagged: usize,
+    /// whether the listener backing the stream has been removed
+    closed: bool,
+    waker: Option<Waker>,
+    listener: Option<Listener<E>>,
+}
+
+impl<E: 'static, T> Channel<E, T> {
+    fn push(&mut self, item: T) {
+        if self.buffer.len() < self.capacity {
+            self.buffer.push_back(item);
+        } else {
+            self.lagged += 1;
+            if self.overflow == Overflow::DropOldest {
+                self.buffer.pop_front();
+                self.buffer.push_back(item);
+            }
+        }
+    }
+}
+
+/// held by the listener backing the stream, so that the stream is woken whenever the listener is removed
+struct Sender<E: 'static, T>(Rc<Cell<Channel<E, T>>>);
+
+impl<E: 'static, T> Drop for Sender<E, T> {
+    fn drop(&mut self) {
+        let waker = {
+            let mut channel = lock(&self.0);
+            channel.closed = true;
+            channel.waker.take()
+        };
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+    }
+}
+
+impl<'x, E: VariableBounds, T: VariableBounds> Subscription<'x, E, T> {
+    /// `map` produces the item for each occurrence of the event, if any
+    pub(crate) fn new<F>(
+        system: System<'x>,
+        target: Option<VariableId>,
+        capacity: usize,
+        overflow: Overflow,
+        map: F,
+    ) -> Self
+    where
+        F: Fn(&System<'x>, &E) -> Option<T> + FnBounds + 'x,
+    {
+        let channel = Rc::new(Cell::new(Channel {
+            buffer: VecDeque::new(),
+            capacity: capacity.max(1),
+            overflow,
+            lagged: 0,
+            closed: false,
+            waker: None,
+            listener: None,
+        }));
+
+        let sender = Sender(channel.clone());
+        let listener = system.listen(target, move |s, e: &E, _, _| {
+            // the item is produced before locking the channel, since producing it might access the system
+            if let Some(item) = map(s, e) {
+                let waker = {
+                    let mut channel = lock(&sender.0);
+                    channel.push(item);
+                    channel.waker.take()
+                };
+                if let Some(waker) = waker {
+                    waker.wake();
+                }
+            }
+        });
+
+        // if the target variable doesn't exist, the listener (and so the sender) has already been dropped
+        lock(&channel).listener = listener;
+
+        Self { system, channel }
+    }
+}
+
+impl<E: 'static, T> Stream for Subscription<'_, E, T> {
+    type Item = Result<T, Lagged>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut channel = lock(&self.channel);
+        if channel.lagged > 0 {
+            let lagged = core::mem::take(&mut channel.lagged);
+            Poll::Ready(Some(Err(Lagged(lagged))))
+        } else if let Some(item) = channel.buffer.pop_front() {
+            Poll::Ready(Some(Ok(item)))
+        } else if channel.closed {
+            Poll::Ready(None)
+        } else {
+            channel.waker = Some(cx.waker().clone());
+            Poll::Pending
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let channel = lock(&self.channel);
+        let len = channel.buffer.len() + usize::from(channel.lagged > 0);
+        (len, channel.closed.then_some(len))
+    }
+}
+
+impl<E: 'static, T> Drop for Subscription<'_, E, T> {
+    fn drop(&mut self) {
+        let listener = lock(&self.channel).listener.take();
+        if let Some(listener) = listener {
+            self.system.silence(listener);
+        }
+    }
+}
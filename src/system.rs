@@ -1,50 +1,265 @@
 use crate::{
-    compat::{Cell, FnBounds, Guard, Handler, Rc, Recipe, Value, VariableBounds},
-    events::{Created, Creating, Deleted, Deleting, Read, Reading, Updated, Updating},
-    listener::{Listener, Vote, Votes},
+    compat::{
+        claim, current_thread, lock, read, try_read, write, Action, Cell, Comparator, EventBounds, FnBounds, FutureBounds,
+        Handler, Message, Rc, Recipe, Setter, Shared, SharedMut, SharedRef, ThreadId, Value, VariableBounds,
+    },
+    effect::Effect,
+    error::Error,
+    transaction::Transaction,
+    events::{
+        Created, Creating, Deleted, Deleting, Detached, Detaching, Dirtied, Read, Reading, Rewired, Rewiring, Updated,
+        Updating,
+    },
+    listener::{Listener, ListenerId, Vote, Votes},
+    next::Next,
+    policy::Policy,
+    task::{noop_waker, AsyncState, Flight, InFlight, Task},
     variable::{Variable, VariableId},
-    Id,
+    id::{Allocator, Id},
 };
 
 use alloc::{
     boxed::Box,
-    collections::{BTreeMap, BTreeSet},
+    collections::{BTreeMap, BTreeSet, VecDeque},
     vec::Vec,
 };
 use core::{
     any::{Any, TypeId},
+    future::Future,
     marker::PhantomData,
+    mem,
+    task::{Context, Poll},
 };
 
+#[cfg(feature = "std")]
+use crate::sender::{EventSender, Inbox};
+#[cfg(all(feature = "std", not(feature = "unsync")))]
+use core::time::Duration;
+#[cfg(feature = "futures-core")]
+use crate::stream::{Overflow, Subscription};
+
 use ahash::RandomState;
 use indexmap::IndexMap;
 
+type Listeners<'x> =
+    BTreeMap<TypeId, BTreeMap<Option<Id>, IndexMap<ListenerId, Handler<'x>, RandomState>>>;
+
+/// The ways in which a variable can be configured on creation
+#[derive(Default, Clone, Copy)]
+struct Options {
+    /// whether the variable is owned by the variable whose recipe is currently running, if any
+    owned: bool,
+    /// whether the variable is only recomputed when it's read
+    lazy: bool,
+    /// whether the variable is an implementation detail, in which case no global `Creating`/`Created`/`Deleted` events are
+    /// emitted for it
+    hidden: bool,
+}
+
+/// Determines whether a variable's recomputed value is equal to its previous value, in which case the recomputation
+/// doesn't propagate any further
+#[derive(Clone)]
+struct Cutoff<'x> {
+    /// since the previous value is passed to the recipe by value, a copy of it is needed for the comparison
+    clone: fn(&Value) -> Option<Value>,
+    eq: Comparator<'x>,
+}
+
+impl<'x> Cutoff<'x> {
+    /// type-erase the given comparator
+    fn new<T, E>(eq: E) -> Self
+    where
+        T: VariableBounds + Clone,
+        E: Fn(&T, &T) -> bool + FnBounds + 'x,
+    {
+        Self {
+            clone: |value| value.downcast_ref::<T>().map(|value| Box::new(value.clone()) as Value),
+            eq: Rc::new(move |prev, next| match (prev.downcast_ref(), next.downcast_ref()) {
+                (Some(prev), Some(next)) => eq(prev, next),
+                _ => false,
+            }),
+        }
+    }
+}
+
+/// Maps writes to a writable variable back into updates of the variables it depends on
+#[derive(Clone)]
+struct Writer<'x> {
+    /// the write is applied to a copy of the variable's value, so that the variable keeps its value while the setter runs
+    clone: fn(&dyn Any) -> Option<Value>,
+    set: Setter<'x>,
+}
+
+/// The value of a variable, which is stored apart from the rest of the system so that it can be accessed without holding
+/// the system
+struct Slot {
+    value: Shared<Value>,
+    /// the threads currently accessing the value, once for each access- it's kept alongside the value rather than with the
+    /// rest of each thread's state, so that accessing the value only requires shared access to the system
+    accessors: Cell<Vec<ThreadId>>,
+}
+
+impl Slot {
+    fn new(value: Value) -> Self {
+        Self {
+            value: Shared::new(value),
+            accessors: Cell::new(Vec::new()),
+        }
+    }
+
+    /// whether the value is being accessed on the current thread
+    fn accessed(&self) -> bool {
+        lock(&self.accessors).contains(&current_thread())
+    }
+
+    /// start accessing the value on the current thread, returning whether the current thread was already accessing it
+    fn open(&self) -> bool {
+        let thread = current_thread();
+        let mut accessors = lock(&self.accessors);
+        let reentrant = accessors.contains(&thread);
+        accessors.push(thread);
+        reentrant
+    }
+
+    /// stop accessing the value on the current thread
+    fn close(&self) {
+        let thread = current_thread();
+        let mut accessors = lock(&self.accessors);
+        if let Some(index) = accessors.iter().rposition(|&accessor| accessor == thread) {
+            accessors.remove(index);
+        }
+    }
+}
+
+/// the state of the system that's specific to each thread acting on it
+#[derive(Default)]
+struct Local {
+    /// while the innermost tracking scope belongs to a variable, system reads establish a dependency between that variable and
+    /// the variable being read- scopes are pushed whenever a recipe runs, and untracked scopes are pushed whenever a handler runs
+    tracking: Vec<Option<Id>>,
+    /// the number of batches currently in progress- while any are in progress, updates aren't propagated
+    batch_depth: usize,
+    /// the variables that have been updated during the batches currently in progress, in the order they were first updated
+    batched: Vec<Id>,
+    /// whether posted events are currently being emitted, so that each one runs to completion before the next
+    draining: bool,
+    /// the variables that are still awaiting recomputation as part of the updates this thread is currently propagating
+    propagating: BTreeSet<Id>,
+    /// the variables awaiting recomputation that are known to be stale, ie: some of the variables they depend on have
+    /// already been recomputed
+    stale: BTreeSet<Id>,
+    /// the variables that have been recomputed as part of the updates this thread is currently propagating, in the order
+    /// in which they were recomputed
+    recomputed: Vec<Id>,
+    /// the effects that need to be re-run once this thread's updates have been fully propagated, in the order they were
+    /// scheduled
+    scheduled: Vec<Id>,
+}
+
+impl Local {
+    /// whether the thread is done acting on the system, in which case there's nothing to keep track of
+    fn idle(&self) -> bool {
+        self.tracking.is_empty()
+            && self.batch_depth == 0
+            && self.batched.is_empty()
+            && !self.draining
+            && self.propagating.is_empty()
+            && self.stale.is_empty()
+            && self.recomputed.is_empty()
+            && self.scheduled.is_empty()
+    }
+}
+
 /// The reactive system
+///
+/// Unless the `unsync` feature is enabled, the system can be shared between threads, with each thread's recipes and
+/// batches being kept track of separately.\
+/// Without the `std` feature however, threads can't be told apart- so the system must only be acted on by one thread at a
+/// time, as otherwise a read on one thread may be tracked as a dependency of a recipe running on another, and a batch on
+/// one thread may defer the updates made on another.
 #[derive(Clone, Default)]
-pub struct System<'x>(pub(crate) Rc<Cell<SystemInner<'x>>>);
+pub struct System<'x>(pub(crate) Rc<Shared<SystemInner<'x>>>);
 
 #[derive(Default)]
 pub(crate) struct SystemInner<'x> {
-    next_id: Id,
-    id_pool: Vec<Id>,
-    /// while a tracking ID is set, system reads establish a dependency between the tracked variable and the variable being read
-    tracking_id: Option<Id>,
+    /// variables and listeners are allocated IDs independently of each other
+    variable_ids: Allocator,
+    listener_ids: Allocator,
+    /// the state of each thread currently acting on the system, so that eg: a read on one thread isn't tracked as a
+    /// dependency of a recipe running on another, a batch on one thread doesn't defer the updates made on another, and
+    /// updates on different threads can be propagated at the same time-
+    /// only a handful of threads act on the system at once, so they're simply searched in order
+    locals: Vec<(ThreadId, Local)>,
+    /// the variable that owns each owned variable
+    owners: BTreeMap<Id, Id>,
+    /// the variables owned by each variable, ie: the reverse of `owners`
+    owned: BTreeMap<Id, BTreeSet<Id>>,
+    /// the hidden variables backing the projected reads made by the latest run of each variable's recipe (or effect), as
+    /// pairs of the variable being read and the hidden variable, in the order the reads were made
+    selectors: BTreeMap<Id, Vec<(Id, Id)>>,
+    /// the hidden variables backing the projected reads made by the previous run of each recipe (or effect) that's
+    /// currently running, which are reused by the projected reads of the same variables rather than being replaced
+    spare: BTreeMap<Id, Vec<(Id, Id)>>,
+    /// emits the `Deleted` event for each variable, since the event is generic over the variable's type
+    deleters: BTreeMap<Id, fn(&mut System<'x>, Id)>,
+    /// the variables that depend on each variable
     dependencies: BTreeMap<Id, BTreeSet<Id>>,
-    values: BTreeMap<Id, Value>,
+    /// the variables that each variable depends on, ie: the reverse of `dependencies`
+    sources: BTreeMap<Id, BTreeSet<Id>>,
+    /// the variables affected by the updates that are still in progress on any thread, ie: whose `Updated` events are yet
+    /// to be emitted
+    unsettled: BTreeSet<Id>,
+    /// the variables that are only recomputed when they're read
+    lazy: BTreeSet<Id>,
+    /// the lazy variables that need to be recomputed the next time they're read
+    dirty: BTreeSet<Id>,
+    /// the variables whose recomputations only propagate if they change their value
+    cutoffs: BTreeMap<Id, Cutoff<'x>>,
+    /// maps writes to writable variables back into updates of the variables they depend on
+    setters: BTreeMap<Id, Writer<'x>>,
+    /// the asynchronous computations that are still in progress for each variable
+    flights: BTreeMap<Id, Rc<Cell<Flight>>>,
+    /// effects are part of the dependency graph just like derived variables, so they're allocated IDs alongside variables
+    effects: BTreeMap<Id, Action<'x>>,
+    /// the events that have been posted but not yet emitted, in the order they were posted
+    posted: Cell<VecDeque<(Option<Id>, Message)>>,
+    /// the events sent to the system from other threads, which are added to the posted events whenever they're drained
+    #[cfg(feature = "std")]
+    inbox: Option<Cell<Inbox>>,
+    /// each value is stored in a slot of its own, so that it can be accessed without holding the rest of the system
+    values: BTreeMap<Id, Rc<Slot>>,
     recipes: BTreeMap<Id, Recipe<'x>>,
-    listeners: BTreeMap<TypeId, BTreeMap<Option<Id>, IndexMap<Id, Handler<'x>, RandomState>>>,
+    /// listeners are registered and triggered independently of any changes to the rest of the system
+    listeners: Shared<Listeners<'x>>,
 }
 
 impl<'x> System<'x> {
-    fn hold(&self) -> Guard<'_, SystemInner<'x>> {
-        #[cfg(not(feature = "unsync"))]
-        {
-            self.0.lock()
-        }
-        #[cfg(feature = "unsync")]
-        {
-            self.0.borrow_mut()
-        }
+    /// gain exclusive access to the system, in order to modify it
+    pub(crate) fn hold(&self) -> SharedMut<'_, SystemInner<'x>> {
+        write(&self.0)
+    }
+
+    /// gain shared access to the system, in order to inspect it alongside any other inspections
+    pub(crate) fn view(&self) -> SharedRef<'_, SystemInner<'x>> {
+        read(&self.0)
+    }
+
+    /// create a new variable that's an implementation detail of the crate, so isn't subject to the global
+    /// `Creating`/`Created`/`Deleted` events
+    pub(crate) fn create_hidden<T, F>(&mut self, recipe: F) -> Result<Variable<T>, Error>
+    where
+        T: VariableBounds,
+        F: Fn(&System<'x>, Option<T>) -> T + FnBounds + 'x,
+    {
+        SystemInner::create(
+            self.clone(),
+            recipe,
+            Options {
+                hidden: true,
+                ..Options::default()
+            },
+            None,
+        )
     }
 
     /// Create a new variable in the reactive system.\
@@ -57,7 +272,7 @@ impl<'x> System<'x> {
     /// difficult to accidentally go wrong, as it will by default be idempotent.
     ///
     /// Returns:
-    /// - [`Err`], if the action was cancelled
+    /// - [`Err`], if the action was cancelled (see [`Error`])
     /// - an [`Ok`] value containing the new variable, otherwise
     ///
     /// # Example
@@ -76,482 +291,2939 @@ impl<'x> System<'x> {
     ///
     /// _ = system.update(a, |v| *v += 1);
     /// assert_eq!(Ok(Some(2)), system.read(b, |v| *v));
+    ///
+    /// // updates propagate through the whole dependency graph, with each dependent only observing up-to-date values
+    /// let c = system.create(move |s, _| {
+    ///     let a = s.read(a, |v| *v).expect("no cancelling listeners registered").expect("`a` was not deleted");
+    ///     let b = s.read(b, |v| *v).expect("no cancelling listeners registered").expect("`b` was not deleted");
+    ///     // `b` is always recomputed before `c`, so this never observes a stale `b`
+    ///     assert_eq!(a + 1, b);
+    ///     a + b
+    /// }).expect("no cancelling listeners registered");
+    /// assert_eq!(Ok(Some(3)), system.read(c, |v| *v));
+    ///
+    /// _ = system.update(a, |v| *v += 1);
+    /// assert_eq!(Ok(Some(5)), system.read(c, |v| *v));
     /// ```
-    pub fn create<T, F>(&mut self, recipe: F) -> Result<Variable<T>, ()>
+    pub fn create<T, F>(&mut self, recipe: F) -> Result<Variable<T>, Error>
     where
         T: VariableBounds,
         F: Fn(&System<'x>, Option<T>) -> T + FnBounds + 'x,
     {
-        SystemInner::create(self.clone(), recipe)
+        SystemInner::create(self.clone(), recipe, Options::default(), None)
     }
 
-    /// Read the value of a variable in the reactive system.\
-    /// The `callback` parameter computes the value to be returned from this function- it receives a read-only reference to the target variable's current value.
+    /// Create a new variable in the reactive system, owned by the variable whose recipe is currently running (if any).\
+    /// This behaves just like [`System::create`], except that the new variable's lifetime is tied to its owner- it's
+    /// deleted whenever its owner is deleted, or whenever its owner's recipe re-runs (since the recipe will presumably create
+    /// a fresh variable in its place).\
+    /// If no recipe is currently running, the new variable has no owner.
     ///
     /// Returns:
-    /// - [`Err`], if the action was cancelled
-    /// - an [`Ok`] value containing [`None`], if the target variable doesn't exist
-    /// - an [`Ok`] value containing a [`Some`] value containing the result of the passed callback, otherwise
+    /// - [`Err`], if the action was cancelled (see [`Error`])
+    /// - an [`Ok`] value containing the new variable, otherwise
     ///
     /// # Example
     /// ```
     /// let mut system = korhah::System::default();
     ///
-    /// let a = system.create(|_, _| 0).expect("no cancelling listeners registered");
+    /// // count the number of deleted variables
+    /// let deleted = system.create(|_, _| 0usize).expect("no cancelling listeners registered");
+    /// system.listen(None, move |s, _: &korhah::events::Deleted<i32>, _, _| {
+    ///     _ = s.update(deleted, |v| *v += 1);
+    /// });
     ///
-    /// assert_eq!(Ok(Some(true)), system.read(a, |v| *v < 5));
+    /// let a = system.create(|_, _| 1).expect("no cancelling listeners registered");
+    /// let b = system.create(move |s, _| {
+    ///     let a = s.read(a, |v| *v).expect("no cancelling listeners registered").expect("`a` exists");
+    ///     // variables can be created from within recipes, and their reads don't interfere with the tracking of `b`
+    ///     let child = s
+    ///         .clone()
+    ///         .create_owned(move |_, _| a * 10)
+    ///         .expect("no cancelling listeners registered");
+    ///     s.read(child, |v| *v).expect("no cancelling listeners registered").expect("`child` exists")
+    /// }).expect("no cancelling listeners registered");
+    /// assert_eq!(Ok(Some(10)), system.read(b, |v| *v));
     ///
-    /// _ = system.delete(a);
-    /// assert_eq!(Ok(None), system.read(a, |v| *v + 1));
+    /// // `b` still depends on `a`, so its recipe re-runs, which replaces its previous child
+    /// _ = system.update(a, |v| *v += 1);
+    /// assert_eq!(Ok(Some(20)), system.read(b, |v| *v));
+    /// assert_eq!(Ok(Some(1)), system.read(deleted, |v| *v));
+    ///
+    /// // deleting `b` deletes the child it owns too
+    /// _ = system.delete(b);
+    /// assert_eq!(Ok(Some(3)), system.read(deleted, |v| *v));
     /// ```
-    pub fn read<T, F, S>(&self, variable: Variable<T>, callback: F) -> Result<Option<S>, ()>
+    pub fn create_owned<T, F>(&mut self, recipe: F) -> Result<Variable<T>, Error>
     where
         T: VariableBounds,
-        F: FnOnce(&T) -> S,
+        F: Fn(&System<'x>, Option<T>) -> T + FnBounds + 'x,
     {
-        SystemInner::read(self.clone(), variable, callback)
+        SystemInner::create(
+            self.clone(),
+            recipe,
+            Options {
+                owned: true,
+                ..Options::default()
+            },
+            None,
+        )
     }
 
-    /// Update the value of a variable in the reactive system.\
-    /// The `callback` parameter performs the update, and optionally returns a value to the caller- it receives
-    /// a mutable reference to the target variable's current value.
+    /// Create a new lazily-computed variable in the reactive system.\
+    /// This behaves just like [`System::create`], except that whenever any of the variables read by the recipe are updated,
+    /// the variable is merely marked as out-of-date rather than being recomputed straight away- the recipe is instead re-run
+    /// the next time the variable is read (including when it's read by the recipe of another variable).\
+    /// The [`Updating`](crate::events::Updating) and [`Updated`](crate::events::Updated) events for the variable are
+    /// likewise only emitted once it's recomputed.
     ///
     /// Returns:
-    /// - [`Err`], if the action was cancelled
-    /// - an [`Ok`] value containing [`None`], if the target variable doesn't exist
-    /// - an [`Ok`] value containing a [`Some`] value containing the result of the passed callback, otherwise
+    /// - [`Err`], if the action was cancelled (see [`Error`])
+    /// - an [`Ok`] value containing the new variable, otherwise
     ///
     /// # Example
     /// ```
+    /// use std::sync::atomic::{AtomicUsize, Ordering};
+    ///
+    /// static RUNS: AtomicUsize = AtomicUsize::new(0);
+    ///
     /// let mut system = korhah::System::default();
     ///
-    /// let a = system.create(|_, _| 0).expect("no cancelling listeners registered");
+    /// let a = system.create(|_, _| 1).expect("no cancelling listeners registered");
+    /// let b = system.create_lazy(move |s, _| {
+    ///     RUNS.fetch_add(1, Ordering::Relaxed);
+    ///     s.read(a, |v| *v * 2).expect("no cancelling listeners registered").expect("`a` exists")
+    /// }).expect("no cancelling listeners registered");
+    /// assert_eq!(1, RUNS.load(Ordering::Relaxed));
     ///
-    /// assert_eq!(Ok(Some(())), system.update(a, |v| *v += 1));
-    /// assert_eq!(Ok(Some(1)), system.read(a, |v| *v));
+    /// // `b` isn't recomputed until it's read
+    /// _ = system.update(a, |v| *v += 1);
+    /// _ = system.update(a, |v| *v += 1);
+    /// assert_eq!(1, RUNS.load(Ordering::Relaxed));
     ///
-    /// _ = system.delete(a);
-    /// assert_eq!(Ok(None), system.update(a, |v| *v += 2));
+    /// assert_eq!(Ok(Some(6)), system.read(b, |v| *v));
+    /// assert_eq!(2, RUNS.load(Ordering::Relaxed));
+    ///
+    /// // `b` is up-to-date, so reading it again doesn't recompute it
+    /// assert_eq!(Ok(Some(6)), system.read(b, |v| *v));
+    /// assert_eq!(2, RUNS.load(Ordering::Relaxed));
     /// ```
-    pub fn update<T, F, S>(&mut self, variable: Variable<T>, callback: F) -> Result<Option<S>, ()>
+    pub fn create_lazy<T, F>(&mut self, recipe: F) -> Result<Variable<T>, Error>
     where
         T: VariableBounds,
-        F: FnOnce(&mut T) -> S,
+        F: Fn(&System<'x>, Option<T>) -> T + FnBounds + 'x,
     {
-        SystemInner::update(self.clone(), variable, callback)
+        SystemInner::create(
+            self.clone(),
+            recipe,
+            Options {
+                lazy: true,
+                ..Options::default()
+            },
+            None,
+        )
     }
 
-    /// Remove a variable from the reactive system.
+    /// Create a new variable in the reactive system, whose recomputations only propagate if they actually change its value.\
+    /// This behaves just like [`System::create`], except that whenever the recipe is re-run, its result is compared against
+    /// the previous value of the variable- if they're equal, the previous value is kept and the recomputation goes no further,
+    /// ie: the variable's dependents aren't recomputed and no [`Updated`](crate::events::Updated) event is emitted for it.\
+    /// Since the previous value is passed to the recipe, a copy of it is kept for the comparison.
     ///
     /// Returns:
-    /// - [`Err`], if the action was cancelled (including if deleting the target variable would leave dangling references)
-    /// - an [`Ok`] value containing [`None`], if the target variable doesn't exist
-    /// - an [`Ok`] value containing the most recent value of the deleted variable, otherwise
+    /// - [`Err`], if the action was cancelled (see [`Error`])
+    /// - an [`Ok`] value containing the new variable, otherwise
     ///
     /// # Example
     /// ```
+    /// use std::sync::atomic::{AtomicUsize, Ordering};
+    ///
+    /// static RUNS: AtomicUsize = AtomicUsize::new(0);
+    ///
     /// let mut system = korhah::System::default();
     ///
-    /// let a = system.create(|_, _| 0).expect("no cancelling listeners registered");
+    /// let a = system.create(|_, _| 1).expect("no cancelling listeners registered");
+    /// let even = system.create_eq(move |s, _| {
+    ///     s.read(a, |v| *v % 2 == 0).expect("no cancelling listeners registered").expect("`a` exists")
+    /// }).expect("no cancelling listeners registered");
     /// let b = system.create(move |s, _| {
-    ///     s
-    ///         .read(a, |v| *v + 1)
-    ///         .expect("no cancelling listeners registered")
-    ///         .expect("`a` was not deleted")
+    ///     RUNS.fetch_add(1, Ordering::Relaxed);
+    ///     s.read(even, |v| *v).expect("no cancelling listeners registered").expect("`even` exists")
     /// }).expect("no cancelling listeners registered");
+    /// assert_eq!(1, RUNS.load(Ordering::Relaxed));
     ///
-    /// // can't delete `a` as `b` depends on it
-    /// assert_eq!(Err(()), system.delete(a));
+    /// // `even` is recomputed, but since its value doesn't change `b` isn't
+    /// _ = system.update(a, |v| *v += 2);
+    /// assert_eq!(1, RUNS.load(Ordering::Relaxed));
     ///
-    /// // if we delete `b` first, we can then delete `a` as it has no dependents
-    /// assert_eq!(Ok(Some(1)), system.delete(b));
-    /// assert_eq!(Ok(Some(0)), system.delete(a));
+    /// _ = system.update(a, |v| *v += 1);
+    /// assert_eq!(Ok(Some(true)), system.read(b, |v| *v));
+    /// assert_eq!(2, RUNS.load(Ordering::Relaxed));
+    /// ```
+    pub fn create_eq<T, F>(&mut self, recipe: F) -> Result<Variable<T>, Error>
+    where
+        T: VariableBounds + Clone + PartialEq,
+        F: Fn(&System<'x>, Option<T>) -> T + FnBounds + 'x,
+    {
+        self.create_eq_by(recipe, T::eq)
+    }
+
+    /// Create a new variable in the reactive system, whose recomputations only propagate if they actually change its value
+    /// according to the given comparator.\
+    /// This behaves just like [`System::create_eq`], except that the `eq` parameter determines whether the recomputed value
+    /// (its second argument) is equal to the previous value (its first argument).
     ///
-    /// // now `a` doesn't exist
-    /// assert_eq!(Ok(None), system.delete(a));
+    /// Returns:
+    /// - [`Err`], if the action was cancelled (see [`Error`])
+    /// - an [`Ok`] value containing the new variable, otherwise
+    ///
+    /// # Example
+    /// ```
+    /// let mut system = korhah::System::default();
+    ///
+    /// let a = system.create(|_, _| 1.0).expect("no cancelling listeners registered");
+    /// // small changes in `a` don't affect `b`
+    /// let b = system.create_eq_by(
+    ///     move |s, _| s.read(a, |v| *v).expect("no cancelling listeners registered").expect("`a` exists"),
+    ///     |prev: &f64, next: &f64| (prev - next).abs() < 0.5,
+    /// ).expect("no cancelling listeners registered");
+    ///
+    /// _ = system.update(a, |v| *v += 0.25);
+    /// assert_eq!(Ok(Some(1.0)), system.read(b, |v| *v));
+    ///
+    /// _ = system.update(a, |v| *v += 0.25);
+    /// assert_eq!(Ok(Some(1.5)), system.read(b, |v| *v));
     /// ```
-    pub fn delete<T>(&mut self, variable: Variable<T>) -> Result<Option<T>, ()>
+    pub fn create_eq_by<T, F, E>(&mut self, recipe: F, eq: E) -> Result<Variable<T>, Error>
     where
-        T: VariableBounds,
+        T: VariableBounds + Clone,
+        F: Fn(&System<'x>, Option<T>) -> T + FnBounds + 'x,
+        E: Fn(&T, &T) -> bool + FnBounds + 'x,
     {
-        SystemInner::delete(self.clone(), variable)
+        SystemInner::create(
+            self.clone(),
+            recipe,
+            Options::default(),
+            Some(Cutoff::new(eq)),
+        )
     }
 
-    /// Register a handler that will be called when a certain event is triggered in the reactive system.\
-    /// A [`None`] target listens for an event in the global scope, whereas a [`Some`] target listens for an event on that specific variable.\
-    /// The `handler` parameter receives a read-write handle to the system, as well as:
-    /// - a reference to the triggered event
-    /// - a mutable reference that can be used to cast the handler's vote on the triggered event (see [`Vote`], [`Votes`])
-    /// - a mutable reference that can be used to abort the triggered event
+    /// Create a new writable variable in the reactive system.\
+    /// The `get` parameter computes the value of the variable, just like the recipe of [`System::create`].\
+    /// The `set` parameter maps writes to the variable back into updates of the variables it depends on- whenever the
+    /// variable is updated, the updated value is passed to it along with a read-write handle to the system, and the
+    /// variable is subsequently recomputed from the updated variables it depends on.\
+    /// The write is applied to a copy of the variable's value, so the variable keeps its current value until it's recomputed.\
+    /// If the setter fails (eg: if one of the updates it makes is cancelled), the update of the variable fails with the same error.
     ///
-    /// Events are uniquely identified by their type, so type annotations are always required for the event argument of the handler.
+    /// Returns:
+    /// - [`Err`], if the action was cancelled (see [`Error`])
+    /// - an [`Ok`] value containing the new variable, otherwise
+    ///
+    /// # Example
+    /// ```
+    /// let mut system = korhah::System::default();
+    ///
+    /// let fahrenheit = system.create(|_, _| 212.0).expect("no cancelling listeners registered");
+    /// let celsius = system.create_writable(
+    ///     move |s, _| {
+    ///         s.read(fahrenheit, |v| (*v - 32.0) * 5.0 / 9.0)
+    ///             .expect("no cancelling listeners registered")
+    ///             .expect("`fahrenheit` exists")
+    ///     },
+    ///     move |s, celsius| s.update(fahrenheit, |v| *v = celsius * 9.0 / 5.0 + 32.0).map(|_| ()),
+    /// ).expect("no cancelling listeners registered");
+    /// assert_eq!(Ok(Some(100.0)), system.read(celsius, |v| *v));
+    ///
+    /// _ = system.update(celsius, |v| *v = 0.0);
+    /// assert_eq!(Ok(Some(32.0)), system.read(fahrenheit, |v| *v));
+    /// assert_eq!(Ok(Some(0.0)), system.read(celsius, |v| *v));
+    ///
+    /// _ = system.update(fahrenheit, |v| *v = 212.0);
+    /// assert_eq!(Ok(Some(100.0)), system.read(celsius, |v| *v));
+    /// ```
+    pub fn create_writable<T, G, S>(&mut self, get: G, set: S) -> Result<Variable<T>, Error>
+    where
+        T: VariableBounds + Clone,
+        G: Fn(&System<'x>, Option<T>) -> T + FnBounds + 'x,
+        S: Fn(&mut System<'x>, T) -> Result<(), Error> + FnBounds + 'x,
+    {
+        let variable = SystemInner::create(self.clone(), get, Options::default(), None)?;
+        // we have to wrap the setter in order to type-erase the value it receives
+        self.hold().setters.insert(
+            variable.id,
+            Writer {
+                clone: |value| value.downcast_ref::<T>().map(|value| Box::new(value.clone()) as Value),
+                set: Rc::new(move |s, value: Value| match value.downcast() {
+                    Ok(value) => set(s, *value),
+                    Err(_) => Err(Error::TypeMismatch),
+                }),
+            },
+        );
+        Ok(variable)
+    }
+
+    /// Create a new variable in the reactive system, whose value is computed asynchronously.\
+    /// The `recipe` parameter starts the computation- it receives a read-only handle to the system, and returns a future
+    /// that resolves to the result of the computation. The variable holds the state of the computation (see [`AsyncState`]).\
+    /// The future is polled once right away, and only the variables it reads until then (ie: before its first `await`) are
+    /// tracked- whenever any of them are updated, the computation is restarted and any computation still in progress is cancelled.\
+    /// If the future isn't immediately ready, the rest of the computation is handed to the `spawn` parameter as a [`Task`],
+    /// to be driven to completion by whichever executor is in use- the task should not be polled from within `spawn` itself.
     ///
     /// Returns:
-    /// - [`None`], if the target variable doesn't exist
-    /// - a [`Some`] value containing the new listener, otherwise
+    /// - [`Err`], if the action was cancelled (see [`Error`])
+    /// - an [`Ok`] value containing the new variable, otherwise
     ///
     /// # Example
     /// ```
-    /// struct CustomEvent {
-    ///     n: usize,
+    /// use korhah::{AsyncState, Task};
+    /// use std::{
+    ///     future::Future,
+    ///     pin::Pin,
+    ///     sync::{atomic::{AtomicBool, Ordering}, Arc, Mutex},
+    ///     task::{Context, Poll, Wake},
+    /// };
+    ///
+    /// // a future that's pending until its gate is opened
+    /// struct Gate(Arc<AtomicBool>);
+    /// impl Future for Gate {
+    ///     type Output = ();
+    ///     fn poll(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<()> {
+    ///         if self.0.load(Ordering::SeqCst) { Poll::Ready(()) } else { Poll::Pending }
+    ///     }
     /// }
     ///
+    /// // a minimal executor that polls its tasks on demand
+    /// # struct Noop;
+    /// # impl Wake for Noop {
+    /// #     fn wake(self: Arc<Self>) {}
+    /// # }
+    /// let tasks = Arc::new(Mutex::new(Vec::<Task>::new()));
+    /// let run = {
+    ///     let tasks = tasks.clone();
+    ///     move || {
+    ///         let waker = Arc::new(Noop).into();
+    ///         let mut cx = Context::from_waker(&waker);
+    ///         let pending = std::mem::take(&mut *tasks.lock().unwrap());
+    ///         for mut task in pending {
+    ///             if Pin::new(&mut task).poll(&mut cx).is_pending() {
+    ///                 tasks.lock().unwrap().push(task);
+    ///             }
+    ///         }
+    ///     }
+    /// };
+    ///
     /// let mut system = korhah::System::default();
     ///
-    /// let listener = system.listen(None, move |_, e: &CustomEvent, vote, abort| {
-    ///     if e.n == 1 {
-    ///         *vote = korhah::Vote::Cancel;
-    ///     } else if e.n == 2 {
-    ///         *abort = true;
-    ///     }
-    /// }).expect("can always listen in the global scope");
+    /// let gate = Arc::new(AtomicBool::new(false));
+    /// let input = system.create(|_, _| 1).expect("no cancelling listeners registered");
+    /// let output = {
+    ///     let gate = gate.clone();
+    ///     let tasks = tasks.clone();
+    ///     system.create_async(
+    ///         move |s| {
+    ///             let n = s.read(input, |v| *v).expect("no cancelling listeners registered").expect("`input` exists");
+    ///             let gate = Gate(gate.clone());
+    ///             async move {
+    ///                 gate.await;
+    ///                 Ok::<_, ()>(n * 10)
+    ///             }
+    ///         },
+    ///         move |task| tasks.lock().unwrap().push(task),
+    ///     ).expect("no cancelling listeners registered")
+    /// };
+    /// assert_eq!(Ok(Some(AsyncState::Pending)), system.read(output, |v| *v));
     ///
-    /// let votes = system.emit(None, &CustomEvent { n: 0 }).expect("not aborted if n == 0");
-    /// assert!(votes.cancel <= votes.proceed);
+    /// gate.store(true, Ordering::SeqCst);
+    /// run();
+    /// assert_eq!(Ok(Some(AsyncState::Ready(10))), system.read(output, |v| *v));
     ///
-    /// let votes = system.emit(None, &CustomEvent { n: 1 }).expect("not aborted if n == 1");
-    /// assert!(votes.cancel >= votes.proceed);
+    /// // updating the input restarts the computation, cancelling the stale ones still in progress
+    /// gate.store(false, Ordering::SeqCst);
+    /// _ = system.update(input, |v| *v += 1);
+    /// _ = system.update(input, |v| *v += 1);
+    /// assert_eq!(Ok(Some(AsyncState::Pending)), system.read(output, |v| *v));
     ///
-    /// assert!(system.emit(None, &CustomEvent { n: 2 }).is_err());
+    /// gate.store(true, Ordering::SeqCst);
+    /// run();
+    /// assert_eq!(Ok(Some(AsyncState::Ready(30))), system.read(output, |v| *v));
+    /// assert!(tasks.lock().unwrap().is_empty());
     /// ```
-    pub fn listen<E, F>(
-        &self,
-        target: impl Into<Option<VariableId>>,
-        handler: F,
-    ) -> Option<Listener<E>>
+    pub fn create_async<T, E, F, Fut, S>(
+        &mut self,
+        recipe: F,
+        spawn: S,
+    ) -> Result<Variable<AsyncState<T, E>>, Error>
     where
-        E: 'static,
-        F: Fn(&mut System<'x>, &E, &mut Vote, &mut bool) + FnBounds + 'x,
+        T: VariableBounds,
+        E: VariableBounds,
+        F: Fn(&System<'x>) -> Fut + FnBounds + 'x,
+        Fut: Future<Output = Result<T, E>> + FutureBounds + 'x,
+        S: Fn(Task<'x>) + FnBounds + 'x,
     {
-        SystemInner::listen(self.clone(), target, handler)
+        SystemInner::create_async(self.clone(), recipe, spawn)
     }
 
-    /// Trigger the given event in the reactive system.\
-    /// A [`None`] target triggers an event in the global scope, whereas a [`Some`] target triggers an event on that specific variable.
+    /// Replace the recipe of a variable in the reactive system.\
+    /// The variable's dependencies are re-tracked according to the new recipe, which is run immediately- it receives the
+    /// current value of the variable as its previous value. Any updates to the variable are then propagated as usual.
     ///
     /// Returns:
-    /// - [`Err`], if any of the triggered handlers aborted the event
-    /// - an [`Ok`] value containing the consensus among the triggered handlers on the event's effects, otherwise (see [`Votes`])
+    /// - [`Err`], if the action was cancelled, or if the variable's recipe is currently running (see [`Error`])
+    /// - an [`Ok`] value containing [`None`], if the target variable doesn't exist
+    /// - an [`Ok`] value containing [`Some`], otherwise
     ///
     /// # Example
     /// ```
-    /// struct CustomEvent;
-    ///
     /// let mut system = korhah::System::default();
     ///
-    /// let triggered = system.create(|_, _| false).expect("no cancelling listeners registered");
-    /// system.listen(None, move |s, _: &CustomEvent, _, abort| {
-    ///     if s.read(triggered, |v| *v)
-    ///         .expect("no cancelling listeners registered")
-    ///         .expect("`a` exists")
-    ///     {
-    ///         *abort = true;
-    ///     } else {
-    ///         _ = s.update(triggered, |v| *v = true);
-    ///     }
-    /// });
+    /// let a = system.create(|_, _| 1).expect("no cancelling listeners registered");
+    /// let b = system.create(|_, _| 10).expect("no cancelling listeners registered");
+    /// let c = system.create(move |s, _| {
+    ///     s.read(a, |v| *v + 1).expect("no cancelling listeners registered").expect("`a` exists")
+    /// }).expect("no cancelling listeners registered");
+    /// let d = system.create(move |s, _| {
+    ///     s.read(c, |v| *v * 2).expect("no cancelling listeners registered").expect("`c` exists")
+    /// }).expect("no cancelling listeners registered");
+    /// assert_eq!(Ok(Some(4)), system.read(d, |v| *v));
     ///
-    /// assert!(system.emit(None, &CustomEvent).is_ok());
-    /// assert!(system.emit(None, &CustomEvent).is_err());
+    /// assert_eq!(Ok(Some(())), system.set_recipe(c, move |s, _| {
+    ///     s.read(b, |v| *v + 1).expect("no cancelling listeners registered").expect("`b` exists")
+    /// }));
+    /// assert_eq!(Ok(Some(22)), system.read(d, |v| *v));
+    ///
+    /// // `c` no longer depends on `a`...
+    /// _ = system.update(a, |v| *v += 1);
+    /// assert_eq!(Ok(Some(11)), system.read(c, |v| *v));
+    ///
+    /// // ...but it does depend on `b`
+    /// _ = system.update(b, |v| *v += 1);
+    /// assert_eq!(Ok(Some(12)), system.read(c, |v| *v));
+    /// assert_eq!(Ok(Some(24)), system.read(d, |v| *v));
     /// ```
-    pub fn emit<E>(&mut self, target: impl Into<Option<VariableId>>, event: &E) -> Result<Votes, ()>
+    pub fn set_recipe<T, F>(&mut self, variable: Variable<T>, recipe: F) -> Result<Option<()>, Error>
     where
-        E: 'static,
+        T: VariableBounds,
+        F: Fn(&System<'x>, Option<T>) -> T + FnBounds + 'x,
     {
-        SystemInner::emit(self.clone(), target, event)
+        SystemInner::rewire(self.clone(), variable, recipe)
     }
 
-    /// Remove the given event listener from the reactive system.
+    /// Turn a variable in the reactive system into a plain variable that holds its most recent value.\
+    /// The variable no longer depends on any other variables, and its recipe is never re-run- it can still be updated as usual.
     ///
     /// Returns:
-    /// - [`None`], if the target event listener doesn't exist
-    /// - [`Some`], otherwise
+    /// - [`Err`], if the action was cancelled, or if the variable's recipe is currently running (see [`Error`])
+    /// - an [`Ok`] value containing [`None`], if the target variable doesn't exist
+    /// - an [`Ok`] value containing [`Some`], otherwise
     ///
     /// # Example
     /// ```
-    /// struct CustomEvent;
-    ///
     /// let mut system = korhah::System::default();
     ///
-    /// let x = system.create(|_, _| 0).expect("no cancelling listeners registered");
-    /// let listener = system.listen(None, move |s, _: &CustomEvent, _, _| {
-    ///     _ = s.update(x, |v| *v += 1);
-    /// }).expect("can always listen in the global scope");
+    /// let a = system.create(|_, _| 1).expect("no cancelling listeners registered");
+    /// let b = system.create(move |s, _| {
+    ///     s.read(a, |v| *v + 1).expect("no cancelling listeners registered").expect("`a` exists")
+    /// }).expect("no cancelling listeners registered");
     ///
-    /// _ = system.emit(None, &CustomEvent);
-    /// assert_eq!(Ok(Some(1)), system.read(x, |v| *v));
-    /// _ = system.emit(None, &CustomEvent);
-    /// assert_eq!(Ok(Some(2)), system.read(x, |v| *v));
+    /// assert_eq!(Ok(Some(())), system.detach(b));
     ///
-    /// assert!(system.silence(listener).is_some());
+    /// _ = system.update(a, |v| *v += 1);
+    /// assert_eq!(Ok(Some(2)), system.read(b, |v| *v));
     ///
-    /// _ = system.emit(None, &CustomEvent);
-    /// assert_eq!(Ok(Some(2)), system.read(x, |v| *v));
+    /// // with no dependents, `a` can be deleted
+    /// assert_eq!(Ok(Some(2)), system.delete(a));
+    /// ```
+    pub fn detach<T>(&mut self, variable: Variable<T>) -> Result<Option<()>, Error>
+    where
+        T: VariableBounds,
+    {
+        SystemInner::detach(self.clone(), variable)
+    }
+
+    /// Read the value of a variable in the reactive system.\
+    /// The `callback` parameter computes the value to be returned from this function- it receives a read-only reference to the target variable's current value.
     ///
-    /// assert!(system.silence(listener).is_none());
+    /// Returns:
+    /// - [`Err`], if the action was cancelled, if it would form a cycle, or if the variable is in the middle of being updated (see [`Error`])
+    /// - an [`Ok`] value containing [`None`], if the target variable doesn't exist
+    /// - an [`Ok`] value containing a [`Some`] value containing the result of the passed callback, otherwise
+    ///
+    /// # Example
     /// ```
-    pub fn silence<E>(&mut self, listener: Listener<E>) -> Option<()>
+    /// let mut system = korhah::System::default();
+    ///
+    /// let a = system.create(|_, _| 0).expect("no cancelling listeners registered");
+    ///
+    /// assert_eq!(Ok(Some(true)), system.read(a, |v| *v < 5));
+    ///
+    /// _ = system.delete(a);
+    /// assert_eq!(Ok(None), system.read(a, |v| *v + 1));
+    /// ```
+    pub fn read<T, F, S>(&self, variable: Variable<T>, callback: F) -> Result<Option<S>, Error>
     where
-        E: 'static,
+        T: VariableBounds,
+        F: FnOnce(&T) -> S,
     {
-        SystemInner::silence(self.clone(), listener)
+        SystemInner::read(self.clone(), variable, callback)
+    }
+
+    /// Read a projection of the value of a variable in the reactive system, tracking only the projected value.\
+    /// The `projection` parameter selects the part of the target variable's value that's of interest- when this is called
+    /// from within a recipe (or effect), the recipe is only re-run when the projected value actually changes, rather than
+    /// whenever the target variable is updated.\
+    /// This is achieved by way of a hidden variable holding the projected value, owned by the running recipe and reused by
+    /// its subsequent runs- as an implementation detail, no `Creating`/`Created`/`Deleted` events are emitted for it.
+    ///
+    /// Returns:
+    /// - [`Err`], if the action was cancelled, or if it would form a cycle (see [`Error`])
+    /// - an [`Ok`] value containing [`None`], if the target variable doesn't exist
+    /// - an [`Ok`] value containing a [`Some`] value containing the projected value, otherwise
+    ///
+    /// # Example
+    /// ```
+    /// use std::sync::atomic::{AtomicUsize, Ordering};
+    ///
+    /// static RUNS: AtomicUsize = AtomicUsize::new(0);
+    ///
+    /// struct Element {
+    ///     text: String,
+    ///     clicks: usize,
+    /// }
+    ///
+    /// let mut system = korhah::System::default();
+    ///
+    /// let el = system.create(|_, _| Element { text: "hi".into(), clicks: 0 }).expect("no cancelling listeners registered");
+    /// let len = system.create(move |s, _| {
+    ///     RUNS.fetch_add(1, Ordering::Relaxed);
+    ///     s.read_projected(el, |el| el.text.clone())
+    ///         .expect("no cancelling listeners registered")
+    ///         .expect("`el` exists")
+    ///         .len()
+    /// }).expect("no cancelling listeners registered");
+    /// assert_eq!(1, RUNS.load(Ordering::Relaxed));
+    ///
+    /// // `len` isn't affected by the other fields of `el`
+    /// _ = system.update(el, |el| el.clicks += 1);
+    /// assert_eq!(1, RUNS.load(Ordering::Relaxed));
+    ///
+    /// _ = system.update(el, |el| el.text.push('!'));
+    /// assert_eq!(Ok(Some(3)), system.read(len, |v| *v));
+    /// assert_eq!(2, RUNS.load(Ordering::Relaxed));
+    /// ```
+    pub fn read_projected<T, P, S>(&self, variable: Variable<T>, projection: P) -> Result<Option<S>, Error>
+    where
+        T: VariableBounds,
+        P: Fn(&T) -> S + FnBounds + 'x,
+        S: VariableBounds + Clone + PartialEq,
+    {
+        SystemInner::read_projected(self.clone(), variable, projection)
+    }
+
+    /// Read the value of a variable in the reactive system without tracking it as a dependency.\
+    /// This behaves just like [`System::read`], except that a recipe (or effect) that peeks at a variable isn't re-run when
+    /// that variable is updated, and that no [`Reading`](crate::events::Reading)/[`Read`](crate::events::Read) events are
+    /// emitted, so the read can't be cancelled.
+    ///
+    /// Returns:
+    /// - [`Err`], if the target variable's value isn't of the expected type, or if it's in the middle of being updated on
+    ///   the same thread (see [`Error`])
+    /// - an [`Ok`] value containing [`None`], if the target variable doesn't exist
+    /// - an [`Ok`] value containing a [`Some`] value containing the result of the passed callback, otherwise
+    ///
+    /// # Example
+    /// ```
+    /// let mut system = korhah::System::default();
+    ///
+    /// let a = system.create(|_, _| 1).expect("no cancelling listeners registered");
+    /// let scale = system.create(|_, _| 10).expect("no cancelling listeners registered");
+    /// let b = system.create(move |s, _| {
+    ///     let a = s.read(a, |v| *v).expect("no cancelling listeners registered").expect("`a` exists");
+    ///     let scale = s.peek(scale, |v| *v).expect("`scale` has the right type").expect("`scale` exists");
+    ///     a * scale
+    /// }).expect("no cancelling listeners registered");
+    /// assert_eq!(Ok(Some(10)), system.read(b, |v| *v));
+    ///
+    /// // `b` doesn't depend on `scale`...
+    /// _ = system.update(scale, |v| *v = 100);
+    /// assert_eq!(Ok(Some(10)), system.read(b, |v| *v));
+    ///
+    /// // ...but it does depend on `a`
+    /// _ = system.update(a, |v| *v += 1);
+    /// assert_eq!(Ok(Some(200)), system.read(b, |v| *v));
+    /// ```
+    pub fn peek<T, F, S>(&self, variable: Variable<T>, callback: F) -> Result<Option<S>, Error>
+    where
+        T: VariableBounds,
+        F: FnOnce(&T) -> S,
+    {
+        SystemInner::peek(self.clone(), variable, callback)
+    }
+
+    /// Perform several actions without tracking any of the variables they read as dependencies.\
+    /// The `f` parameter receives a read-only handle to the system- reads made through it behave as usual (including
+    /// emitting events), except that they don't make the currently-running recipe (or effect) depend on the variables read.
+    ///
+    /// Returns the result of the passed function.
+    ///
+    /// # Example
+    /// ```
+    /// let mut system = korhah::System::default();
+    ///
+    /// let a = system.create(|_, _| 1).expect("no cancelling listeners registered");
+    /// let b = system.create(|_, _| 2).expect("no cancelling listeners registered");
+    /// let sum = system.create(move |s, _| {
+    ///     s.untracked(|s| {
+    ///         let a = s.read(a, |v| *v).expect("no cancelling listeners registered").expect("`a` exists");
+    ///         let b = s.read(b, |v| *v).expect("no cancelling listeners registered").expect("`b` exists");
+    ///         a + b
+    ///     })
+    /// }).expect("no cancelling listeners registered");
+    ///
+    /// _ = system.update(a, |v| *v += 1);
+    /// _ = system.update(b, |v| *v += 1);
+    /// assert_eq!(Ok(Some(3)), system.read(sum, |v| *v));
+    ///
+    /// // with no dependents, `a` and `b` can be deleted
+    /// assert_eq!(Ok(Some(2)), system.delete(a));
+    /// assert_eq!(Ok(Some(3)), system.delete(b));
+    /// ```
+    pub fn untracked<R, F>(&self, f: F) -> R
+    where
+        F: FnOnce(&System<'x>) -> R,
+    {
+        SystemInner::untracked(self.clone(), f)
+    }
+
+    /// Update the value of a variable in the reactive system.\
+    /// The `callback` parameter performs the update, and optionally returns a value to the caller- it receives
+    /// a mutable reference to the target variable's current value.
+    ///
+    /// Returns:
+    /// - [`Err`], if the action was cancelled, if it would re-enter an update that is still being propagated, or if the variable (or any variable depending on it) is in the middle of being accessed (see [`Error`])
+    /// - an [`Ok`] value containing [`None`], if the target variable doesn't exist
+    /// - an [`Ok`] value containing a [`Some`] value containing the result of the passed callback, otherwise
+    ///
+    /// # Example
+    /// ```
+    /// let mut system = korhah::System::default();
+    ///
+    /// let a = system.create(|_, _| 0).expect("no cancelling listeners registered");
+    ///
+    /// assert_eq!(Ok(Some(())), system.update(a, |v| *v += 1));
+    /// assert_eq!(Ok(Some(1)), system.read(a, |v| *v));
+    ///
+    /// _ = system.delete(a);
+    /// assert_eq!(Ok(None), system.update(a, |v| *v += 2));
+    /// ```
+    pub fn update<T, F, S>(&mut self, variable: Variable<T>, callback: F) -> Result<Option<S>, Error>
+    where
+        T: VariableBounds,
+        F: FnOnce(&mut T) -> S,
+    {
+        SystemInner::update(self.clone(), variable, callback)
+    }
+
+    /// Perform several actions on the reactive system as a single batch.\
+    /// Updates made during the batch take effect immediately, however their propagation is deferred until the batch has
+    /// finished- at that point, each variable affected by the batch is recomputed at most once, and a single
+    /// [`Updated`](crate::events::Updated) event is emitted for each updated or recomputed variable.\
+    /// Batches can be nested, in which case the propagation is deferred until the outermost batch has finished.
+    ///
+    /// Returns:
+    /// - [`Err`], if the updates couldn't be propagated (see [`Error::Cycle`])
+    /// - an [`Ok`] value containing the result of the passed callback, otherwise
+    ///
+    /// # Example
+    /// ```
+    /// use std::sync::atomic::{AtomicUsize, Ordering};
+    ///
+    /// static RUNS: AtomicUsize = AtomicUsize::new(0);
+    ///
+    /// let mut system = korhah::System::default();
+    ///
+    /// let a = system.create(|_, _| 1).expect("no cancelling listeners registered");
+    /// let b = system.create(|_, _| 2).expect("no cancelling listeners registered");
+    /// let sum = system.create(move |s, _| {
+    ///     RUNS.fetch_add(1, Ordering::Relaxed);
+    ///     let a = s.read(a, |v| *v).expect("no cancelling listeners registered").expect("`a` exists");
+    ///     let b = s.read(b, |v| *v).expect("no cancelling listeners registered").expect("`b` exists");
+    ///     a + b
+    /// }).expect("no cancelling listeners registered");
+    /// assert_eq!(1, RUNS.load(Ordering::Relaxed));
+    ///
+    /// let ret = system.batch(|s| {
+    ///     _ = s.update(a, |v| *v = 10);
+    ///     _ = s.update(b, |v| *v = 20);
+    ///     // the updates haven't been propagated yet
+    ///     s.read(sum, |v| *v)
+    /// });
+    /// assert_eq!(Ok(Ok(Some(3))), ret);
+    ///
+    /// // `sum` was only recomputed once for both updates
+    /// assert_eq!(Ok(Some(30)), system.read(sum, |v| *v));
+    /// assert_eq!(2, RUNS.load(Ordering::Relaxed));
+    /// ```
+    pub fn batch<R, F>(&mut self, f: F) -> Result<R, Error>
+    where
+        F: FnOnce(&mut System<'x>) -> R,
+    {
+        SystemInner::batch(self.clone(), f)
+    }
+
+    /// Perform several updates on the reactive system as a single all-or-nothing batch (see [`System::batch`]).\
+    /// If the passed callback returns an [`Err`], or if any of the updates made via [`Transaction::update`] fail (eg: if
+    /// their [`Updating`](crate::events::Updating) event is cancelled), all of those updates are undone without being
+    /// propagated.\
+    /// Writable variables (see [`System::create_writable`]) are updated by way of their setters, so their updates are
+    /// undone by writing their previous values back through their setters.
+    ///
+    /// Returns:
+    /// - [`Err`], if the transaction failed, or if its updates couldn't be propagated (see [`Error`])
+    /// - an [`Ok`] value containing the result of the passed callback, otherwise
+    ///
+    /// # Example
+    /// ```
+    /// let mut system = korhah::System::default();
+    ///
+    /// let a = system.create(|_, _| 1).expect("no cancelling listeners registered");
+    /// let b = system.create(|_, _| 2).expect("no cancelling listeners registered");
+    ///
+    /// // updates to `b` are always cancelled
+    /// system.listen(b, |_, _: &korhah::events::Updating, vote, _| {
+    ///     *vote = korhah::Vote::Cancel;
+    /// });
+    ///
+    /// let ret = system.transaction(|tx| {
+    ///     tx.update(a, |v| *v = 10)?;
+    ///     tx.update(b, |v| *v = 20)?;
+    ///     Ok(())
+    /// });
+    /// assert!(matches!(ret, Err(korhah::Error::Cancelled(_))));
+    ///
+    /// // the update to `a` was undone
+    /// assert_eq!(Ok(Some(1)), system.read(a, |v| *v));
+    /// assert_eq!(Ok(Some(2)), system.read(b, |v| *v));
+    ///
+    /// // updates made directly through the system aren't undone, and are still propagated
+    /// let c = system.create(|_, _| 3).expect("no cancelling listeners registered");
+    /// let double = system.create(move |s, _| {
+    ///     s.read(c, |v| *v * 2).expect("no cancelling listeners registered").expect("`c` exists")
+    /// }).expect("no cancelling listeners registered");
+    /// let ret = system.transaction(|tx| {
+    ///     tx.update(a, |v| *v = 10)?;
+    ///     _ = korhah::System::update(tx, c, |v| *v = 7);
+    ///     tx.update(b, |v| *v = 20)?;
+    ///     Ok(())
+    /// });
+    /// assert!(ret.is_err());
+    /// assert_eq!(Ok(Some(1)), system.read(a, |v| *v));
+    /// assert_eq!(Ok(Some(7)), system.read(c, |v| *v));
+    /// assert_eq!(Ok(Some(14)), system.read(double, |v| *v));
+    /// ```
+    pub fn transaction<R, F>(&mut self, f: F) -> Result<R, Error>
+    where
+        F: FnOnce(&mut Transaction<'_, 'x>) -> Result<R, Error>,
+    {
+        SystemInner::transaction(self.clone(), f)
+    }
+
+    /// Remove a variable from the reactive system.
+    ///
+    /// Returns:
+    /// - [`Err`], if the action was cancelled, or if deleting the target variable would leave dangling references (see [`Error`])
+    /// - an [`Ok`] value containing [`None`], if the target variable doesn't exist
+    /// - an [`Ok`] value containing the most recent value of the deleted variable, otherwise
+    ///
+    /// # Example
+    /// ```
+    /// let mut system = korhah::System::default();
+    ///
+    /// let a = system.create(|_, _| 0).expect("no cancelling listeners registered");
+    /// let b = system.create(move |s, _| {
+    ///     s
+    ///         .read(a, |v| *v + 1)
+    ///         .expect("no cancelling listeners registered")
+    ///         .expect("`a` was not deleted")
+    /// }).expect("no cancelling listeners registered");
+    ///
+    /// // can't delete `a` as `b` depends on it
+    /// assert_eq!(Err(korhah::Error::HasDependents(vec![b.into()])), system.delete(a));
+    ///
+    /// // if we delete `b` first, we can then delete `a` as it has no dependents
+    /// assert_eq!(Ok(Some(1)), system.delete(b));
+    /// assert_eq!(Ok(Some(0)), system.delete(a));
+    ///
+    /// // now `a` doesn't exist
+    /// assert_eq!(Ok(None), system.delete(a));
+    ///
+    /// // handles to deleted variables never alias variables that are created later on
+    /// let c = system.create(|_, _| "c").expect("no cancelling listeners registered");
+    /// assert_eq!(Ok(None), system.read(a, |v| *v));
+    /// assert_eq!(Ok(None), system.read(b, |v| *v));
+    /// assert_eq!(Ok(Some("c")), system.read(c, |v| *v));
+    /// ```
+    pub fn delete<T>(&mut self, variable: Variable<T>) -> Result<Option<T>, Error>
+    where
+        T: VariableBounds,
+    {
+        SystemInner::delete(self.clone(), variable, Policy::Refuse)
+    }
+
+    /// Remove a variable from the reactive system, dealing with the variables that depend on it according to the given policy.\
+    /// With [`Policy::Refuse`], this behaves just like [`System::delete`].\
+    /// With [`Policy::Cascade`], every variable that (transitively) depends on the target variable is deleted along with it-
+    /// the `Deleting` event is emitted for each of them (dependents first), and if any of them are cancelled then nothing is deleted.\
+    /// With [`Policy::Orphan`], the variables that depend on the target variable are turned into plain variables that keep
+    /// their most recent value, ie: their recipes are never re-run.
+    ///
+    /// Returns:
+    /// - [`Err`], if the action was cancelled, or if deleting the target variable would leave dangling references (see [`Error`])
+    /// - an [`Ok`] value containing [`None`], if the target variable doesn't exist
+    /// - an [`Ok`] value containing the most recent value of the deleted variable, otherwise
+    ///
+    /// # Example
+    /// ```
+    /// use korhah::Policy;
+    ///
+    /// let mut system = korhah::System::default();
+    ///
+    /// let a = system.create(|_, _| 1).expect("no cancelling listeners registered");
+    /// let b = system.create(move |s, _| {
+    ///     s.read(a, |v| *v + 1).expect("no cancelling listeners registered").expect("`a` exists")
+    /// }).expect("no cancelling listeners registered");
+    /// let c = system.create(move |s, _| {
+    ///     s.read(b, |v| *v + 1).expect("no cancelling listeners registered").expect("`b` exists")
+    /// }).expect("no cancelling listeners registered");
+    ///
+    /// // deleting `b` leaves `c` frozen with its most recent value
+    /// assert_eq!(Ok(Some(2)), system.delete_with(b, Policy::Orphan));
+    /// _ = system.update(c, |v| *v *= 10);
+    /// assert_eq!(Ok(Some(30)), system.read(c, |v| *v));
+    ///
+    /// let d = system.create(move |s, _| {
+    ///     s.read(a, |v| *v * 2).expect("no cancelling listeners registered").expect("`a` exists")
+    /// }).expect("no cancelling listeners registered");
+    /// let e = system.create(move |s, _| {
+    ///     s.read(d, |v| *v * 2).expect("no cancelling listeners registered").expect("`d` exists")
+    /// }).expect("no cancelling listeners registered");
+    ///
+    /// // deleting `a` takes `d` and `e` with it
+    /// assert_eq!(Ok(Some(1)), system.delete_with(a, Policy::Cascade));
+    /// assert_eq!(Ok(None), system.read(d, |v| *v));
+    /// assert_eq!(Ok(None), system.read(e, |v| *v));
+    /// assert_eq!(Ok(Some(30)), system.read(c, |v| *v));
+    /// ```
+    pub fn delete_with<T>(&mut self, variable: Variable<T>, policy: Policy) -> Result<Option<T>, Error>
+    where
+        T: VariableBounds,
+    {
+        SystemInner::delete(self.clone(), variable, policy)
+    }
+
+    /// Register a handler that will be called when a certain event is triggered in the reactive system.\
+    /// A [`None`] target listens for an event in the global scope, whereas a [`Some`] target listens for an event on that specific variable.\
+    /// The `handler` parameter receives a read-write handle to the system, as well as:
+    /// - a reference to the triggered event
+    /// - a mutable reference that can be used to cast the handler's vote on the triggered event (see [`Vote`], [`Votes`])
+    /// - a mutable reference that can be used to abort the triggered event
+    ///
+    /// Events are uniquely identified by their type, so type annotations are always required for the event argument of the handler.
+    ///
+    /// Returns:
+    /// - [`None`], if the target variable doesn't exist
+    /// - a [`Some`] value containing the new listener, otherwise
+    ///
+    /// # Example
+    /// ```
+    /// struct CustomEvent {
+    ///     n: usize,
+    /// }
+    ///
+    /// let mut system = korhah::System::default();
+    ///
+    /// let listener = system.listen(None, move |_, e: &CustomEvent, vote, abort| {
+    ///     if e.n == 1 {
+    ///         *vote = korhah::Vote::Cancel;
+    ///     } else if e.n == 2 {
+    ///         *abort = true;
+    ///     }
+    /// }).expect("can always listen in the global scope");
+    ///
+    /// let votes = system.emit(None, &CustomEvent { n: 0 }).expect("not aborted if n == 0");
+    /// assert!(votes.cancel <= votes.proceed);
+    ///
+    /// let votes = system.emit(None, &CustomEvent { n: 1 }).expect("not aborted if n == 1");
+    /// assert!(votes.cancel >= votes.proceed);
+    ///
+    /// assert_eq!(Err(korhah::Error::Aborted(listener.into())), system.emit(None, &CustomEvent { n: 2 }));
+    /// ```
+    pub fn listen<E, F>(
+        &self,
+        target: impl Into<Option<VariableId>>,
+        handler: F,
+    ) -> Option<Listener<E>>
+    where
+        E: 'static,
+        F: Fn(&mut System<'x>, &E, &mut Vote, &mut bool) + FnBounds + 'x,
+    {
+        SystemInner::listen(self.clone(), target, handler)
+    }
+
+    /// Trigger the given event in the reactive system.\
+    /// A [`None`] target triggers an event in the global scope, whereas a [`Some`] target triggers an event on that specific variable.
+    ///
+    /// Returns:
+    /// - [`Err`], if any of the triggered handlers aborted the event (see [`Error::Aborted`])
+    /// - an [`Ok`] value containing the consensus among the triggered handlers on the event's effects, otherwise (see [`Votes`])
+    ///
+    /// # Example
+    /// ```
+    /// struct CustomEvent;
+    ///
+    /// let mut system = korhah::System::default();
+    ///
+    /// let triggered = system.create(|_, _| false).expect("no cancelling listeners registered");
+    /// system.listen(None, move |s, _: &CustomEvent, _, abort| {
+    ///     if s.read(triggered, |v| *v)
+    ///         .expect("no cancelling listeners registered")
+    ///         .expect("`a` exists")
+    ///     {
+    ///         *abort = true;
+    ///     } else {
+    ///         _ = s.update(triggered, |v| *v = true);
+    ///     }
+    /// });
+    ///
+    /// assert!(system.emit(None, &CustomEvent).is_ok());
+    /// assert!(system.emit(None, &CustomEvent).is_err());
+    /// ```
+    pub fn emit<E>(&mut self, target: impl Into<Option<VariableId>>, event: &E) -> Result<Votes, Error>
+    where
+        E: 'static,
+    {
+        SystemInner::emit(self.clone(), target, event)
+    }
+
+    /// Queue the given event to be triggered in the reactive system later on, once the queue is drained (see
+    /// [`System::run_one`] and [`System::run_until_idle`]).\
+    /// A [`None`] target queues an event in the global scope, whereas a [`Some`] target queues an event on that specific variable.\
+    /// Unlike [`System::emit`], which triggers handlers immediately (interleaving any events they emit with the event
+    /// being emitted), queued events are triggered one at a time in the order they were posted, each running to
+    /// completion before the next- events posted by handlers are simply added to the back of the queue.\
+    /// Queued events are moved into the queue rather than shared, so they needn't be `Sync`- the same events can be queued
+    /// from other threads by way of an `EventSender` (see `System::sender`).
+    ///
+    /// # Example
+    /// ```
+    /// struct Ping(u8);
+    ///
+    /// let mut system = korhah::System::default();
+    ///
+    /// let log = system.create(|_, _| Vec::new()).expect("no cancelling listeners registered");
+    /// system.listen(None, move |s, e: &Ping, _, _| {
+    ///     _ = s.update(log, |v| v.push(("start", e.0)));
+    ///     if e.0 < 2 {
+    ///         s.post(None, Ping(e.0 + 1));
+    ///     }
+    ///     _ = s.update(log, |v| v.push(("end", e.0)));
+    /// });
+    ///
+    /// system.post(None, Ping(0));
+    /// assert_eq!(Ok(Some(0)), system.read(log, |v| v.len()));
+    ///
+    /// assert_eq!(3, system.run_until_idle());
+    /// assert_eq!(
+    ///     Ok(Some(vec![("start", 0), ("end", 0), ("start", 1), ("end", 1), ("start", 2), ("end", 2)])),
+    ///     system.read(log, |v| v.clone()),
+    /// );
+    ///
+    /// // events that aren't `Sync` can be queued too
+    /// struct Note(std::cell::Cell<u8>);
+    ///
+    /// system.listen(None, move |s, e: &Note, _, _| {
+    ///     _ = s.update(log, |v| v.push(("note", e.0.get())));
+    /// });
+    /// system.post(None, Note(std::cell::Cell::new(3)));
+    /// assert_eq!(1, system.run_until_idle());
+    /// assert_eq!(Ok(Some(Some(("note", 3)))), system.read(log, |v| v.last().copied()));
+    /// ```
+    pub fn post<E>(&self, target: impl Into<Option<VariableId>>, event: E)
+    where
+        E: EventBounds,
+    {
+        let target_id = target.into().map(|VariableId(id)| id);
+        lock(&self.view().posted).push_back((target_id, Box::new(event)));
+    }
+
+    /// Trigger the oldest event queued in the reactive system (see [`System::post`]).\
+    /// Handlers of queued events can't drain the queue themselves- calling this from within one has no effect, so that
+    /// every queued event runs to completion before the next.
+    ///
+    /// Returns:
+    /// - [`None`], if there were no queued events (or the queue is already being drained)
+    /// - a [`Some`] value containing the outcome of triggering the event, otherwise (see [`System::emit`])
+    ///
+    /// # Example
+    /// ```
+    /// struct CustomEvent;
+    ///
+    /// let mut system = korhah::System::default();
+    ///
+    /// let x = system.create(|_, _| 0).expect("no cancelling listeners registered");
+    /// system.listen(x, move |s, _: &CustomEvent, _, _| {
+    ///     _ = s.update(x, |v| *v += 1);
+    /// });
+    ///
+    /// system.post(x, CustomEvent);
+    /// system.post(x, CustomEvent);
+    /// assert_eq!(Ok(Some(0)), system.read(x, |v| *v));
+    ///
+    /// assert!(matches!(system.run_one(), Some(Ok(_))));
+    /// assert_eq!(Ok(Some(1)), system.read(x, |v| *v));
+    /// assert!(matches!(system.run_one(), Some(Ok(_))));
+    /// assert_eq!(Ok(Some(2)), system.read(x, |v| *v));
+    /// assert!(system.run_one().is_none());
+    /// ```
+    pub fn run_one(&mut self) -> Option<Result<Votes, Error>> {
+        SystemInner::run_one(self.clone())
+    }
+
+    /// Trigger the events queued in the reactive system until there are none left, including any events queued by their
+    /// handlers along the way (see [`System::post`]).\
+    /// Handlers of queued events can't drain the queue themselves- calling this from within one has no effect, so that
+    /// every queued event runs to completion before the next.
+    ///
+    /// Returns the number of events that were triggered.
+    ///
+    /// # Example
+    /// ```
+    /// struct CustomEvent;
+    ///
+    /// let mut system = korhah::System::default();
+    ///
+    /// let x = system.create(|_, _| 0).expect("no cancelling listeners registered");
+    /// system.listen(x, move |s, _: &CustomEvent, _, _| {
+    ///     _ = s.update(x, |v| *v += 1);
+    /// });
+    ///
+    /// for _ in 0..3 {
+    ///     system.post(x, CustomEvent);
+    /// }
+    /// assert_eq!(3, system.run_until_idle());
+    /// assert_eq!(Ok(Some(3)), system.read(x, |v| *v));
+    /// assert_eq!(0, system.run_until_idle());
+    /// ```
+    pub fn run_until_idle(&mut self) -> usize {
+        let mut count = 0;
+        while SystemInner::run_one(self.clone()).is_some() {
+            count += 1;
+        }
+        count
+    }
+
+    /// Get a handle for queueing events in the reactive system from other threads.\
+    /// Events sent through it are queued alongside posted events, and are triggered whenever the queue is drained on the
+    /// thread that owns the system (see [`System::post`]).
+    ///
+    /// # Example
+    /// ```
+    /// struct Input(u32);
+    ///
+    /// let mut system = korhah::System::default();
+    ///
+    /// let total = system.create(|_, _| 0).expect("no cancelling listeners registered");
+    /// system.listen(total, move |s, e: &Input, _, _| {
+    ///     _ = s.update(total, |v| *v += e.0);
+    /// });
+    ///
+    /// let workers = (1..=3)
+    ///     .map(|i| {
+    ///         let sender = system.sender();
+    ///         std::thread::spawn(move || sender.send(total, Input(i)))
+    ///     })
+    ///     .collect::<Vec<_>>();
+    /// for worker in workers {
+    ///     assert_eq!(Some(()), worker.join().expect("the worker didn't panic"));
+    /// }
+    ///
+    /// assert_eq!(Ok(Some(0)), system.read(total, |v| *v));
+    /// assert_eq!(3, system.run_until_idle());
+    /// assert_eq!(Ok(Some(6)), system.read(total, |v| *v));
+    ///
+    /// // once the system is dropped, events can no longer be sent to it
+    /// let sender = system.sender();
+    /// drop(system);
+    /// assert_eq!(None, sender.send(None, Input(0)));
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn sender(&self) -> EventSender {
+        let mut inner = self.hold();
+        let inbox = inner.inbox.get_or_insert_with(|| Cell::new(Inbox::default()));
+        let sender = lock(inbox).sender.clone();
+        EventSender(sender)
+    }
+
+    /// Remove the given event listener from the reactive system.
+    ///
+    /// Returns:
+    /// - [`None`], if the target event listener doesn't exist
+    /// - [`Some`], otherwise
+    ///
+    /// # Example
+    /// ```
+    /// struct CustomEvent;
+    ///
+    /// let mut system = korhah::System::default();
+    ///
+    /// let x = system.create(|_, _| 0).expect("no cancelling listeners registered");
+    /// let listener = system.listen(None, move |s, _: &CustomEvent, _, _| {
+    ///     _ = s.update(x, |v| *v += 1);
+    /// }).expect("can always listen in the global scope");
+    ///
+    /// _ = system.emit(None, &CustomEvent);
+    /// assert_eq!(Ok(Some(1)), system.read(x, |v| *v));
+    /// _ = system.emit(None, &CustomEvent);
+    /// assert_eq!(Ok(Some(2)), system.read(x, |v| *v));
+    ///
+    /// assert!(system.silence(listener).is_some());
+    ///
+    /// _ = system.emit(None, &CustomEvent);
+    /// assert_eq!(Ok(Some(2)), system.read(x, |v| *v));
+    ///
+    /// assert!(system.silence(listener).is_none());
+    ///
+    /// // the IDs of silenced listeners are recycled, but a stale handle never aliases the listener that reuses its ID
+    /// let other = system.listen(None, move |s, _: &CustomEvent, _, _| {
+    ///     _ = s.update(x, |v| *v += 1);
+    /// }).expect("can always listen in the global scope");
+    /// assert!(system.silence(listener).is_none());
+    /// assert_ne!(korhah::ListenerId::from(listener), korhah::ListenerId::from(other));
+    ///
+    /// _ = system.emit(None, &CustomEvent);
+    /// assert_eq!(Ok(Some(3)), system.read(x, |v| *v));
+    /// ```
+    pub fn silence<E>(&mut self, listener: Listener<E>) -> Option<()>
+    where
+        E: 'static,
+    {
+        SystemInner::silence(self.clone(), listener)
+    }
+
+    /// Wait for the next occurrence of a certain event in the reactive system.\
+    /// A [`None`] target waits for an event in the global scope, whereas a [`Some`] target waits for an event on that specific variable.\
+    /// The returned future registers a listener right away, so only occurrences of the event after this call are considered.
+    ///
+    /// Returns a future that resolves to:
+    /// - [`None`], if the target variable doesn't exist, or is deleted before the event occurs
+    /// - a [`Some`] value containing a copy of the event, otherwise
+    ///
+    /// # Example
+    /// ```
+    /// use std::{future::Future, task::Poll};
+    /// # use std::{sync::Arc, task::{Context, Wake}};
+    /// # struct Noop;
+    /// # impl Wake for Noop {
+    /// #     fn wake(self: Arc<Self>) {}
+    /// # }
+    /// # let waker = Arc::new(Noop).into();
+    /// # let mut cx = Context::from_waker(&waker);
+    ///
+    /// #[derive(Debug, Clone, PartialEq)]
+    /// struct CustomEvent(i32);
+    ///
+    /// let mut system = korhah::System::default();
+    ///
+    /// let mut next = Box::pin(system.next::<CustomEvent>(None));
+    /// assert_eq!(Poll::Pending, next.as_mut().poll(&mut cx));
+    ///
+    /// _ = system.emit(None, &CustomEvent(1));
+    /// _ = system.emit(None, &CustomEvent(2));
+    /// assert_eq!(Poll::Ready(Some(CustomEvent(1))), next.as_mut().poll(&mut cx));
+    ///
+    /// let a = system.create(|_, _| 0).expect("no cancelling listeners registered");
+    /// let mut next = Box::pin(system.next::<CustomEvent>(a));
+    /// _ = system.delete(a);
+    /// assert_eq!(Poll::Ready(None), next.as_mut().poll(&mut cx));
+    /// ```
+    pub fn next<E>(&self, target: impl Into<Option<VariableId>>) -> Next<'x, E>
+    where
+        E: VariableBounds + Clone,
+    {
+        Next::new(self.clone(), target.into())
+    }
+
+    /// Wait for the next update of a variable in the reactive system, ie: the next [`Updated`] event emitted on it.
+    ///
+    /// Returns a future that resolves to:
+    /// - [`None`], if the target variable doesn't exist, or is deleted before it's updated
+    /// - a [`Some`] value, otherwise
+    ///
+    /// # Example
+    /// ```
+    /// use std::{future::Future, task::Poll};
+    /// # use std::{sync::Arc, task::{Context, Wake}};
+    /// # struct Noop;
+    /// # impl Wake for Noop {
+    /// #     fn wake(self: Arc<Self>) {}
+    /// # }
+    /// # let waker = Arc::new(Noop).into();
+    /// # let mut cx = Context::from_waker(&waker);
+    ///
+    /// let mut system = korhah::System::default();
+    ///
+    /// let a = system.create(|_, _| 0).expect("no cancelling listeners registered");
+    /// let b = system.create(move |s, _| {
+    ///     s.read(a, |v| *v * 2).expect("no cancelling listeners registered").expect("`a` exists")
+    /// }).expect("no cancelling listeners registered");
+    ///
+    /// let mut changed = Box::pin(system.changed(b));
+    /// assert!(changed.as_mut().poll(&mut cx).is_pending());
+    ///
+    /// _ = system.update(a, |v| *v += 1);
+    /// assert!(matches!(changed.as_mut().poll(&mut cx), Poll::Ready(Some(_))));
+    /// ```
+    pub fn changed<T>(&self, variable: Variable<T>) -> Next<'x, Updated>
+    where
+        T: VariableBounds,
+    {
+        self.next(variable)
+    }
+
+    /// Block the current thread until the value of a variable in the reactive system satisfies the given predicate.\
+    /// The predicate is checked right away, and then again whenever the variable is updated (ie: whenever an [`Updated`]
+    /// event is emitted on it)- the thread is parked in between, so it doesn't consume any resources while it waits.\
+    /// Since the thread is blocked, the variable has to be updated by another thread for the wait to be of any use.\
+    /// A lazy variable (see [`create_lazy`](Self::create_lazy)) is checked whenever it's marked as out-of-date, which
+    /// recomputes it.\
+    /// A timeout too large to be represented is treated as no timeout at all.
+    ///
+    /// Returns:
+    /// - [`Err`], if the variable's value couldn't be checked (see [`Error`])
+    /// - an [`Ok`] value containing [`None`], if the target variable doesn't exist, or is deleted while waiting
+    /// - an [`Ok`] value containing a [`Some`] value containing `true` if the predicate was satisfied, or `false` if the
+    ///   timeout elapsed first
+    ///
+    /// # Example
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// let mut system = korhah::System::default();
+    ///
+    /// let progress = system.create(|_, _| 0).expect("no cancelling listeners registered");
+    /// assert_eq!(Ok(Some(false)), system.wait_until(progress, |v| *v == 100, Duration::from_millis(10)));
+    ///
+    /// let worker = {
+    ///     let mut system = system.clone();
+    ///     std::thread::spawn(move || {
+    ///         for _ in 0..100 {
+    ///             assert_eq!(Ok(Some(())), system.update(progress, |v| *v += 1));
+    ///         }
+    ///     })
+    /// };
+    /// assert_eq!(Ok(Some(true)), system.wait_until(progress, |v| *v == 100, Duration::MAX));
+    /// # assert!(worker.join().is_ok());
+    ///
+    /// _ = system.delete(progress);
+    /// assert_eq!(Ok(None), system.wait_until(progress, |v| *v == 100, Duration::from_secs(60)));
+    /// ```
+    #[cfg(all(feature = "std", not(feature = "unsync")))]
+    pub fn wait_until<T, P>(&self, variable: Variable<T>, predicate: P, timeout: Duration) -> Result<Option<bool>, Error>
+    where
+        T: VariableBounds,
+        P: Fn(&T) -> bool,
+    {
+        crate::wait::wait_until(self, variable, predicate, timeout)
+    }
+
+    /// Subscribe to every occurrence of a certain event in the reactive system.\
+    /// A [`None`] target subscribes to events in the global scope, whereas a [`Some`] target subscribes to events on that
+    /// specific variable.\
+    /// Up to `capacity` events (at least one) are buffered until they're consumed- beyond that, events are discarded
+    /// according to the `overflow` policy, and the stream reports how many were discarded (see [`Lagged`](crate::Lagged)).
+    ///
+    /// Returns a stream of the events, which ends if the target variable doesn't exist (or is deleted).
+    ///
+    /// # Example
+    /// ```
+    /// use futures_core::Stream;
+    /// use korhah::{Lagged, Overflow};
+    /// use std::task::Poll;
+    /// # use std::{sync::Arc, task::{Context, Wake}};
+    /// # struct Noop;
+    /// # impl Wake for Noop {
+    /// #     fn wake(self: Arc<Self>) {}
+    /// # }
+    /// # let waker = Arc::new(Noop).into();
+    /// # let mut cx = Context::from_waker(&waker);
+    ///
+    /// #[derive(Debug, Clone, PartialEq)]
+    /// struct CustomEvent(u8);
+    ///
+    /// let mut system = korhah::System::default();
+    ///
+    /// let mut events = Box::pin(system.event_stream::<CustomEvent>(None, 2, Overflow::DropOldest));
+    /// assert!(events.as_mut().poll_next(&mut cx).is_pending());
+    ///
+    /// // emitting is never blocked, even once the buffer is full
+    /// for i in 0..3 {
+    ///     _ = system.emit(None, &CustomEvent(i));
+    /// }
+    /// assert_eq!(Poll::Ready(Some(Err(Lagged(1)))), events.as_mut().poll_next(&mut cx));
+    /// assert_eq!(Poll::Ready(Some(Ok(CustomEvent(1)))), events.as_mut().poll_next(&mut cx));
+    /// assert_eq!(Poll::Ready(Some(Ok(CustomEvent(2)))), events.as_mut().poll_next(&mut cx));
+    /// assert!(events.as_mut().poll_next(&mut cx).is_pending());
+    /// ```
+    #[cfg(feature = "futures-core")]
+    pub fn event_stream<E>(
+        &self,
+        target: impl Into<Option<VariableId>>,
+        capacity: usize,
+        overflow: Overflow,
+    ) -> Subscription<'x, E, E>
+    where
+        E: VariableBounds + Clone,
+    {
+        Subscription::new(self.clone(), target.into(), capacity, overflow, |_, e: &E| {
+            Some(e.clone())
+        })
+    }
+
+    /// Subscribe to the value of a variable in the reactive system, ie: its value after each [`Updated`] event emitted on it.\
+    /// Up to `capacity` values (at least one) are buffered until they're consumed- beyond that, values are discarded
+    /// according to the `overflow` policy, and the stream reports how many were discarded (see [`Lagged`](crate::Lagged)).
+    ///
+    /// Returns a stream of the variable's values, which ends if the variable doesn't exist (or is deleted).
+    ///
+    /// # Example
+    /// ```
+    /// use futures_core::Stream;
+    /// use korhah::Overflow;
+    /// use std::task::Poll;
+    /// # use std::{sync::Arc, task::{Context, Wake}};
+    /// # struct Noop;
+    /// # impl Wake for Noop {
+    /// #     fn wake(self: Arc<Self>) {}
+    /// # }
+    /// # let waker = Arc::new(Noop).into();
+    /// # let mut cx = Context::from_waker(&waker);
+    ///
+    /// let mut system = korhah::System::default();
+    ///
+    /// let a = system.create(|_, _| 0).expect("no cancelling listeners registered");
+    /// let b = system.create(move |s, _| {
+    ///     s.read(a, |v| *v * 2).expect("no cancelling listeners registered").expect("`a` exists")
+    /// }).expect("no cancelling listeners registered");
+    ///
+    /// let mut values = Box::pin(system.watch_stream(b, 8, Overflow::DropOldest));
+    /// _ = system.update(a, |v| *v += 1);
+    /// _ = system.update(a, |v| *v += 1);
+    /// assert_eq!(Poll::Ready(Some(Ok(2))), values.as_mut().poll_next(&mut cx));
+    /// assert_eq!(Poll::Ready(Some(Ok(4))), values.as_mut().poll_next(&mut cx));
+    /// assert!(values.as_mut().poll_next(&mut cx).is_pending());
+    ///
+    /// _ = system.delete(b);
+    /// assert_eq!(Poll::Ready(None), values.as_mut().poll_next(&mut cx));
+    /// ```
+    #[cfg(feature = "futures-core")]
+    pub fn watch_stream<T>(
+        &self,
+        variable: Variable<T>,
+        capacity: usize,
+        overflow: Overflow,
+    ) -> Subscription<'x, Updated, T>
+    where
+        T: VariableBounds + Clone,
+    {
+        Subscription::new(self.clone(), Some(variable.into()), capacity, overflow, move |s, _: &Updated| {
+            s.peek(variable, T::clone).ok().flatten()
+        })
+    }
+
+    /// Register an effect in the reactive system, which is run immediately.\
+    /// The `effect` parameter receives a read-write handle to the system- just like a recipe, the variables it reads are
+    /// automatically tracked, and whenever any of them are updated the effect is re-run.\
+    /// Effects are only re-run once an update has been fully propagated (ie: after every affected variable has been
+    /// recomputed and their `Updated` events have been emitted), so they always observe a consistent system.\
+    /// An effect isn't re-run as a result of its own updates, and unlike derived variables, effects don't prevent the
+    /// variables they read from being deleted.
+    ///
+    /// Returns the new effect, which can be used to remove it from the system (see [`System::stop`]).
+    ///
+    /// # Example
+    /// ```
+    /// let mut system = korhah::System::default();
+    ///
+    /// let a = system.create(|_, _| 0).expect("no cancelling listeners registered");
+    /// let log = system.create(|_, _| Vec::new()).expect("no cancelling listeners registered");
+    ///
+    /// system.effect(move |s| {
+    ///     let a = s.read(a, |v| *v).expect("no cancelling listeners registered").expect("`a` exists");
+    ///     _ = s.update(log, |v| v.push(a));
+    /// });
+    /// assert_eq!(Ok(Some(vec![0])), system.read(log, |v| v.clone()));
+    ///
+    /// _ = system.update(a, |v| *v += 1);
+    /// assert_eq!(Ok(Some(vec![0, 1])), system.read(log, |v| v.clone()));
+    /// ```
+    pub fn effect<F>(&mut self, effect: F) -> Effect
+    where
+        F: Fn(&mut System<'x>) + FnBounds + 'x,
+    {
+        SystemInner::effect(self.clone(), effect)
+    }
+
+    /// Remove the given effect from the reactive system, along with any variables it owns.
+    ///
+    /// Returns:
+    /// - [`None`], if the target effect doesn't exist
+    /// - [`Some`], otherwise
+    ///
+    /// # Example
+    /// ```
+    /// let mut system = korhah::System::default();
+    ///
+    /// let a = system.create(|_, _| 0).expect("no cancelling listeners registered");
+    /// let runs = system.create(|_, _| 0).expect("no cancelling listeners registered");
+    ///
+    /// let effect = system.effect(move |s| {
+    ///     _ = s.read(a, |_| ());
+    ///     _ = s.update(runs, |v| *v += 1);
+    /// });
+    /// _ = system.update(a, |v| *v += 1);
+    /// assert_eq!(Ok(Some(2)), system.read(runs, |v| *v));
+    ///
+    /// assert!(system.stop(effect).is_some());
+    ///
+    /// _ = system.update(a, |v| *v += 1);
+    /// assert_eq!(Ok(Some(2)), system.read(runs, |v| *v));
+    ///
+    /// assert!(system.stop(effect).is_none());
+    /// ```
+    pub fn stop(&mut self, effect: Effect) -> Option<()> {
+        SystemInner::stop(self.clone(), effect)
+    }
+}
+
+impl<'x> SystemInner<'x> {
+    /// add a new variable to the reactive system
+    fn create<T, F>(
+        mut this: System<'x>,
+        recipe: F,
+        options: Options,
+        cutoff: Option<Cutoff<'x>>,
+    ) -> Result<Variable<T>, Error>
+    where
+        T: VariableBounds,
+        F: Fn(&System<'x>, Option<T>) -> T + FnBounds + 'x,
+    {
+        let id = this.hold().variable_ids.allocate();
+
+        if options.owned {
+            // the new variable is owned by whichever variable's recipe is currently running, if any
+            let owner = this.view().scope();
+            if let Some(owner) = owner {
+                this.hold().owners.insert(id, owner);
+                this.hold().owned.entry(owner).or_default().insert(id);
+            }
+        }
+
+        // ensure the new variable's dependencies, if any, are tracked
+        this.hold().enter(Some(id));
+        let value = recipe(&this, None);
+        this.hold().exit();
+
+        let event = Creating { value };
+        // since the variable is not yet created, it's impossible to listen for its local events at this point, so
+        // the `Creating` event is only emitted in the global scope- hidden variables are an implementation detail, so their
+        // creation isn't subject to it
+        let creating = if options.hidden {
+            Ok(())
+        } else {
+            proceed(this.emit(None, &event))
+        };
+        if let Err(err) = creating {
+            // since the `Creating` event has been cancelled, the ID we selected hasn't ended up being used, so we free it
+            // along with any dependencies its recipe established and any variables its recipe created
+            this.hold().untrack(id);
+            this.hold().disown(id);
+            this.hold().selectors.remove(&id);
+            SystemInner::abandon(&this, id);
+            SystemInner::dispose(this.clone(), id);
+            this.hold().variable_ids.free(id);
+            return Err(err);
+        }
+
+        // reclaim the newly-created value after having temporarily loaned it to the `Creating` event
+        let value = event.value;
+        // store the type-erased initial value
+        this.hold().store(id, Box::new(value));
+        if options.lazy {
+            this.hold().lazy.insert(id);
+        }
+        if let Some(cutoff) = cutoff {
+            this.hold().cutoffs.insert(id, cutoff);
+        }
+        if !options.hidden {
+            this.hold().deleters.insert(id, |s, id| {
+                // we don't care if `Deleted` events are cancelled as there are no subsequent actions to take
+                _ = s.emit(
+                    None,
+                    &Deleted {
+                        _source: Variable::<T> {
+                            id,
+                            _t: PhantomData,
+                        },
+                    },
+                );
+            });
+        }
+        this.hold().recipes.insert(id, SystemInner::wrap(id, recipe));
+
+        let variable = Variable {
+            id,
+            _t: PhantomData,
+        };
+
+        // same as the `Creating` event, the `Created` event is emitted only in the global scope as it's impossible to
+        // listen for it locally ahead of time
+        // we don't care if the `Created` event is cancelled, as it doesn't prevent any subsequent actions
+        if !options.hidden {
+            _ = this.emit(None, &Created { source: variable });
+        }
+
+        Ok(variable)
+    }
+
+    /// add a new variable to the reactive system, whose value is computed asynchronously
+    fn create_async<T, E, F, Fut, S>(this: System<'x>, recipe: F, spawn: S) -> Result<Variable<AsyncState<T, E>>, Error>
+    where
+        T: VariableBounds,
+        E: VariableBounds,
+        F: Fn(&System<'x>) -> Fut + FnBounds + 'x,
+        Fut: Future<Output = Result<T, E>> + FutureBounds + 'x,
+        S: Fn(Task<'x>) + FnBounds + 'x,
+    {
+        SystemInner::create(
+            this,
+            move |s, _| {
+                // recipes always run within their own variable's tracking scope
+                let id = s.view().scope().expect("recipes run within their own tracking scope");
+
+                // the future is polled once within the tracking scope, so that the variables it reads before its first
+                // `await` are tracked
+                let mut future = Box::pin(recipe(s));
+                let waker = noop_waker();
+                match future.as_mut().poll(&mut Context::from_waker(&waker)) {
+                    Poll::Ready(result) => result.into(),
+                    Poll::Pending => {
+                        let flight = Rc::new(Cell::new(Flight::default()));
+                        s.hold().flights.insert(id, flight.clone());
+                        spawn(Task(Box::pin(InFlight {
+                            system: s.clone(),
+                            variable: Variable {
+                                id,
+                                _t: PhantomData,
+                            },
+                            future,
+                            flight,
+                            settled: None,
+                        })));
+                        AsyncState::Pending
+                    }
+                }
+            },
+            Options::default(),
+            None,
+        )
+    }
+
+    /// store the result of an asynchronous computation, unless it has been superseded in the meantime
+    ///
+    /// the result is handed back if it can't be stored just yet (eg: if the variable is being read on this thread), so
+    /// that storing it can be retried later on
+    pub(crate) fn settle<T, E>(
+        mut this: System<'x>,
+        variable: Variable<AsyncState<T, E>>,
+        flight: &Rc<Cell<Flight>>,
+        state: AsyncState<T, E>,
+    ) -> Result<(), AsyncState<T, E>>
+    where
+        T: VariableBounds,
+        E: VariableBounds,
+    {
+        let current = |inner: &SystemInner<'x>| {
+            inner
+                .flights
+                .get(&variable.id)
+                .map_or(false, |current| Rc::ptr_eq(current, flight))
+        };
+        if !current(&this.view()) {
+            return Ok(());
+        }
+
+        // the computation is only done with once its result has been stored, or once storing it has been cancelled
+        let mut state = Some(state);
+        let result = this.update(variable, |value| {
+            if let Some(state) = state.take() {
+                *value = state;
+            }
+        });
+        match (result, state) {
+            (Err(Error::Reentrancy | Error::Cycle(_)), Some(state)) => Err(state),
+            _ => {
+                let mut inner = this.hold();
+                if current(&inner) {
+                    inner.flights.remove(&variable.id);
+                }
+                Ok(())
+            }
+        }
+    }
+
+    /// cancel the asynchronous computation that's still in progress for the given variable, if any
+    fn abandon(this: &System<'x>, id: Id) {
+        let flight = this.hold().flights.remove(&id);
+        if let Some(flight) = flight {
+            Flight::cancel(&flight);
+        }
+    }
+
+    /// wrap the given recipe for the given variable, in order to supply the previous value of the variable as an argument to
+    /// it, to type-erase its return value, and to determine whether its return value differs from the previous value
+    fn wrap<T, F>(id: Id, recipe: F) -> Recipe<'x>
+    where
+        T: VariableBounds,
+        F: Fn(&System<'x>, Option<T>) -> T + FnBounds + 'x,
+    {
+        Rc::new(move |s, prev| {
+            let cutoff = s.hold().cutoffs.get(&id).cloned();
+            match cutoff {
+                Some(cutoff) => {
+                    let copy = prev
+                        .as_ref()
+                        .and_then(cutoff.clone)
+                        .and_then(|v| v.downcast().ok())
+                        .map(|v| *v);
+                    let next: Value = Box::new(recipe(s, copy));
+                    match prev {
+                        // the previous value is kept if it's equal to the recomputed value
+                        Some(prev) if (cutoff.eq)(&prev, &next) => (prev, false),
+                        _ => (next, true),
+                    }
+                }
+                None => {
+                    let prev = prev.and_then(|v| v.downcast().ok()).map(|v| *v);
+                    (Box::new(recipe(s, prev)), true)
+                }
+            }
+        })
+    }
+
+    /// read the value of a variable in the reactive system
+    fn read<T, F, S>(
+        mut this: System<'x>,
+        variable: Variable<T>,
+        callback: F,
+    ) -> Result<Option<S>, Error>
+    where
+        T: VariableBounds,
+        F: FnOnce(&T) -> S,
+    {
+        // store the tracking scope serparately to avoid deadlock
+        let dependent = this.view().scope();
+        if let Some(dependent) = dependent {
+            // if the variable being read already (transitively) depends on the variable whose recipe is running, tracking
+            // this read would form a cycle, so the read is refused
+            let cycle = this.view().path(dependent, variable.id);
+            if let Some(cycle) = cycle {
+                return Err(Error::Cycle(cycle.into_iter().map(VariableId).collect()));
+            }
+        }
+
+        if !this.view().values.contains_key(&variable.id) {
+            // the target variable doesn't exist so we ignore this request
+            return Ok(None);
+        }
+
+        let computing = this.view().computing();
+        if computing {
+            // a recipe never observes a mix of stale and fresh values, even if it's reading the variable for the first time
+            // or without tracking it
+            SystemInner::catch_up(&this, variable.id);
+        }
+
+        // lazy variables are brought up-to-date before they're read
+        SystemInner::refresh(this.clone(), variable.id);
+
+        // the `Reading` event is cancellable
+        proceed(this.emit(variable, &Reading))?;
+
+        if let Some(dependent) = dependent {
+            // this variable is being read as part of a new variable's recipe, so we track the dependency
+            // in order to trigger updates when the new variable is changed, and to prevent dangling references
+            this.hold().track(variable.id, dependent);
+        }
+
+        // compute the result of the passed callback
+        let ret = match SystemInner::access(&this, variable.id, callback)? {
+            Some(ret) => ret,
+            // the variable has been deleted in the meantime (eg: by a handler, or on another thread)
+            None => return Ok(None),
+        };
+
+        // we don't care if `Read` events are cancelled as there are no subsequent actions to take
+        _ = this.emit(variable, &Read);
+
+        Ok(Some(ret))
+    }
+
+    /// read a projection of the value of a variable in the reactive system
+    fn read_projected<T, P, S>(this: System<'x>, variable: Variable<T>, projection: P) -> Result<Option<S>, Error>
+    where
+        T: VariableBounds,
+        P: Fn(&T) -> S + FnBounds + 'x,
+        S: VariableBounds + Clone + PartialEq,
+    {
+        let dependent = this.view().scope();
+        let dependent = match dependent {
+            Some(dependent) => dependent,
+            // there's nothing to track, so the projection can be applied directly
+            None => return SystemInner::read(this, variable, projection),
+        };
+
+        if !this.view().values.contains_key(&variable.id) {
+            // the target variable doesn't exist so we ignore this request
+            return Ok(None);
+        }
+
+        // the projection is tracked by a hidden variable of its own, which only propagates when the projected value changes-
+        // it's owned by the running recipe, and the recipe's next run reuses it for its projected read of the same variable
+        let recipe = move |s: &System<'x>, _: Option<Option<S>>| s.read(variable, &projection).ok().flatten();
+        let spare = this.hold().spare.get_mut(&dependent).and_then(|spare| {
+            let position = spare.iter().position(|&(source, _)| source == variable.id)?;
+            Some(spare.remove(position).1)
+        });
+        let selector = match spare.filter(|id| this.view().values.contains_key(id)) {
+            Some(id) => {
+                // the latest projection may capture different state than the previous one, so the value is recomputed
+                this.hold().recipes.insert(id, SystemInner::wrap(id, recipe));
+                this.hold().owned.entry(dependent).or_default().insert(id);
+                SystemInner::recompute(&this, id);
+                Variable {
+                    id,
+                    _t: PhantomData,
+                }
+            }
+            None => SystemInner::create(
+                this.clone(),
+                recipe,
+                Options {
+                    owned: true,
+                    hidden: true,
+                    ..Options::default()
+                },
+                Some(Cutoff::new(Option::<S>::eq)),
+            )?,
+        };
+        this.hold()
+            .selectors
+            .entry(dependent)
+            .or_default()
+            .push((variable.id, selector.id));
+
+        SystemInner::read(this, selector, Option::clone).map(Option::flatten)
+    }
+
+    /// read the value of a variable in the reactive system without tracking it or emitting any events
+    fn peek<T, F, S>(this: System<'x>, variable: Variable<T>, callback: F) -> Result<Option<S>, Error>
+    where
+        T: VariableBounds,
+        F: FnOnce(&T) -> S,
+    {
+        if !this.view().values.contains_key(&variable.id) {
+            // the target variable doesn't exist so we ignore this request
+            return Ok(None);
+        }
+
+        // the value being peeked at should be the same as if it were read, so a variable that's awaiting recomputation is
+        // still caught up, and lazy variables are still brought up-to-date
+        let computing = this.view().computing();
+        if computing {
+            SystemInner::catch_up(&this, variable.id);
+        }
+        SystemInner::refresh(this.clone(), variable.id);
+
+        SystemInner::access(&this, variable.id, callback)
+    }
+
+    /// pass the value of the given variable to the given callback- only the variable's own slot is held while the callback
+    /// runs, so that the callback is free to act on the rest of the system
+    fn access<T, F, S>(this: &System<'x>, id: Id, callback: F) -> Result<Option<S>, Error>
+    where
+        T: VariableBounds,
+        F: FnOnce(&T) -> S,
+    {
+        // the variable may have been deleted since its existence was last checked, as the system isn't held in between
+        let slot = match this.view().values.get(&id).cloned() {
+            Some(slot) => slot,
+            None => return Ok(None),
+        };
+        let reentrant = slot.open();
+        // the value can be read alongside other reads, but not while it's being updated- an update on another thread is
+        // waited on, whereas an update on this thread can't finish until this read has
+        let guard = if reentrant {
+            try_read(&slot.value).ok_or(Error::Reentrancy)
+        } else {
+            Ok(read(&slot.value))
+        };
+        // the type system should prevent downcasting errors here, however they're still reported rather than panicking
+        let ret = guard.and_then(|guard| guard.downcast_ref().map(callback).ok_or(Error::TypeMismatch));
+        slot.close();
+        ret.map(Some)
+    }
+
+    /// pass the value of the given variable to the given callback for it to be modified, as with [`SystemInner::access`]
+    fn access_mut<T, F, S>(this: &System<'x>, id: Id, callback: F) -> Result<Option<S>, Error>
+    where
+        T: VariableBounds,
+        F: FnOnce(&mut T) -> S,
+    {
+        let slot = match this.view().values.get(&id).cloned() {
+            Some(slot) => slot,
+            None => return Ok(None),
+        };
+        // the accesses on other threads are waited on, whereas those on this thread can't finish until this one has
+        let ret = if slot.open() {
+            Err(Error::Reentrancy)
+        } else {
+            let mut guard = claim(&slot.value);
+            // the type system should prevent downcasting errors here, however they're still reported rather than panicking
+            guard.downcast_mut().map(callback).ok_or(Error::TypeMismatch)
+        };
+        slot.close();
+        ret.map(Some)
+    }
+
+    /// perform several actions in an untracked scope
+    fn untracked<R, F>(this: System<'x>, f: F) -> R
+    where
+        F: FnOnce(&System<'x>) -> R,
+    {
+        this.hold().enter(None);
+        let ret = f(&this);
+        this.hold().exit();
+        ret
+    }
+
+    /// update the value of a variable in the reactive system
+    fn update<T, F, S>(
+        mut this: System<'x>,
+        variable: Variable<T>,
+        callback: F,
+    ) -> Result<Option<S>, Error>
+    where
+        T: VariableBounds,
+        F: FnOnce(&mut T) -> S,
+    {
+        // a variable can't be updated while its own recipe is running, as the recipe's result would overwrite the update-
+        // nor while its value is being accessed, as the update would pull the value out from under the accessor- however
+        // only a recipe or an access on this thread can't finish before the update, whereas those on other threads are waited
+        // on (which is only done once the update is known not to be re-entrant, as the other threads may be waiting on this one)
+        if this.view().reentrant(variable.id) {
+            return Err(Error::Reentrancy);
+        }
+        SystemInner::wait(&this, variable.id);
+
+        if !this.view().values.contains_key(&variable.id) {
+            // the target variable doesn't exist so we ignore this request
+            return Ok(None);
+        }
+
+        // the `Updating` event is cancellable
+        proceed(this.emit(variable, &Updating))?;
+
+        let writer = this.view().setters.get(&variable.id).cloned();
+        if let Some(writer) = writer {
+            return SystemInner::write(this, variable, callback, writer);
+        }
+
+        // the propagation is planned before the variable is actually updated, so that the update can be refused without
+        // any effects if it would interfere with an update that's still being propagated
+        let batching = this.view().batching();
+        let order = if batching {
+            // the propagation of batched updates is deferred until the batch is committed, however it must still be
+            // possible at that point
+            this.hold().order(&[variable.id])?;
+            Vec::new()
+        } else {
+            this.hold().plan(&[variable.id])?
+        };
+
+        // invoke the callback that will update the variable
+        let ret = match SystemInner::access_mut(&this, variable.id, callback) {
+            Ok(Some(ret)) => ret,
+            // the planned propagation won't take place after all, either because the variable has been deleted in the
+            // meantime (eg: by a handler, or on another thread) or because its value couldn't be accessed
+            Ok(None) => {
+                this.hold().unplan(&order);
+                return Ok(None);
+            }
+            Err(err) => {
+                this.hold().unplan(&order);
+                return Err(err);
+            }
+        };
+
+        // if the variable is lazy, this update supersedes any pending recomputation
+        this.hold().dirty.remove(&variable.id);
+
+        if batching {
+            // the update will be propagated along with the rest of the batch once it's committed
+            this.hold().defer(variable.id);
+            return Ok(Some(ret));
+        }
+
+        // we must recompute the values of any variables that (transitively) depend on the just-changed variable
+        let recomputed = SystemInner::propagate(this.clone(), &[variable.id], &order);
+
+        // the `Updated` events are only emitted once every affected variable has been recomputed, so that handlers never
+        // observe a partially-propagated system
+        // we don't care if these `Updated` events are cancelled as there are no subsequent actions to take for the dependent variables
+        for dependent in recomputed {
+            _ = this.emit(VariableId(dependent), &Updated);
+        }
+
+        // the updated variable itself is settled by now, so its `Updated` handlers are free to update it again (eg: to undo
+        // the update)- its dependents only settle once they have all been dealt with
+        // we don't care if this `Updated` event is cancelled as there are no subsequent actions to take
+        this.hold().conclude(&[variable.id]);
+        _ = this.emit(variable, &Updated);
+        this.hold().conclude(&order);
+
+        // now that the update has been fully propagated, the effects it affected can be re-run
+        SystemInner::flush(this);
+
+        Ok(Some(ret))
+    }
+
+    /// replace the recipe of a variable in the reactive system
+    fn rewire<T, F>(mut this: System<'x>, variable: Variable<T>, recipe: F) -> Result<Option<()>, Error>
+    where
+        T: VariableBounds,
+        F: Fn(&System<'x>, Option<T>) -> T + FnBounds + 'x,
+    {
+        // a variable's recipe can't be replaced while it's running or awaiting recomputation, as the old recipe's result
+        // would otherwise overwrite the new recipe's result- a recomputation on another thread is simply waited on
+        if this.view().reentrant(variable.id) {
+            return Err(Error::Reentrancy);
+        }
+        SystemInner::wait(&this, variable.id);
+        if !this.view().values.contains_key(&variable.id) {
+            // the target variable doesn't exist so we ignore this request
+            return Ok(None);
+        }
+        if this.hold().busy(variable.id) {
+            return Err(Error::Reentrancy);
+        }
+
+        // the `Rewiring` event is cancellable
+        proceed(this.emit(variable, &Rewiring))?;
+
+        // as with updates, the propagation is planned before the variable is actually recomputed
+        let batching = this.view().batching();
+        let order = if batching {
+            this.hold().order(&[variable.id])?;
+            Vec::new()
+        } else {
+            this.hold().plan(&[variable.id])?
+        };
+
+        // the variable is recomputed right away, regardless of whether it's lazy
+        this.hold().recipes.insert(variable.id, SystemInner::wrap(variable.id, recipe));
+        this.hold().dirty.remove(&variable.id);
+        let changed = SystemInner::recompute(&this, variable.id);
+
+        if batching {
+            // the update will be propagated along with the rest of the batch once it's committed
+            if changed {
+                this.hold().defer(variable.id);
+            }
+            // we don't care if the `Rewired` event is cancelled as there are no subsequent actions to take
+            _ = this.emit(variable, &Rewired);
+            return Ok(Some(()));
+        }
+
+        // if the recomputation didn't change the variable's value, there's nothing to propagate
+        let sources = if changed { alloc::vec![variable.id] } else { Vec::new() };
+        let recomputed = SystemInner::propagate(this.clone(), &sources, &order);
+
+        // we don't care if these `Updated` or `Rewired` events are cancelled as there are no subsequent actions to take
+        for dependent in recomputed {
+            _ = this.emit(VariableId(dependent), &Updated);
+        }
+        // as with updates, the rewired variable itself is settled before its own `Updated` event is emitted
+        this.hold().conclude(&[variable.id]);
+        if changed {
+            _ = this.emit(variable, &Updated);
+        }
+        this.hold().conclude(&order);
+        _ = this.emit(variable, &Rewired);
+
+        SystemInner::flush(this);
+
+        Ok(Some(()))
+    }
+
+    /// turn a variable in the reactive system into a plain variable
+    fn detach<T>(mut this: System<'x>, variable: Variable<T>) -> Result<Option<()>, Error>
+    where
+        T: VariableBounds,
+    {
+        // a variable can't be detached while its recipe is running or awaiting recomputation, as the recipe would otherwise
+        // make the variable depend on other variables again- a recomputation on another thread is simply waited on
+        if this.view().reentrant(variable.id) {
+            return Err(Error::Reentrancy);
+        }
+        SystemInner::wait(&this, variable.id);
+        if !this.view().values.contains_key(&variable.id) {
+            // the target variable doesn't exist so we ignore this request
+            return Ok(None);
+        }
+        if this.hold().busy(variable.id) {
+            return Err(Error::Reentrancy);
+        }
+
+        // the `Detaching` event is cancellable
+        proceed(this.emit(variable, &Detaching))?;
+
+        SystemInner::freeze(this.clone(), variable.id);
+
+        // we don't care if the `Detached` event is cancelled as there are no subsequent actions to take
+        _ = this.emit(variable, &Detached);
+
+        Ok(Some(()))
+    }
+
+    /// update the value of a writable variable by way of its setter
+    fn write<T, F, S>(mut this: System<'x>, variable: Variable<T>, callback: F, writer: Writer<'x>) -> Result<Option<S>, Error>
+    where
+        T: VariableBounds,
+        F: FnOnce(&mut T) -> S,
+    {
+        // the update is applied to a copy of the variable's current value, which is then handed over to the setter- the
+        // variable itself is left in place, to be recomputed by the updates the setter makes
+        let copy = match SystemInner::access(&this, variable.id, |value: &T| (writer.clone)(value))? {
+            Some(copy) => copy,
+            None => return Ok(None),
+        };
+        // the type system should prevent downcasting errors here, however they're still reported rather than panicking
+        let mut value = copy.and_then(|copy| copy.downcast::<T>().ok()).ok_or(Error::TypeMismatch)?;
+        let ret = callback(&mut value);
+
+        // the setter isn't part of any recipe, so the reads it makes aren't tracked
+        this.hold().enter(None);
+        let result = (writer.set)(&mut this, value);
+        this.hold().exit();
+
+        result.map(|_| Some(ret))
+    }
+
+    /// determine the order in which the variables affected by an update of the given source variables should be recomputed
+    fn order(&self, sources: &[Id]) -> Result<Vec<Id>, Error> {
+        // visiting the dependents in topological order guarantees that each recipe only runs once all of the variables it
+        // depends on are up-to-date, so every dependent is recomputed at most once and never observes a mix of stale and fresh values
+        let order = self.topological_order(sources);
+
+        // if any of the affected variables are still affected by another update that's in progress (eg: one of their
+        // `Updating` or `Updated` handlers has in turn updated one of the variables they depend on), propagating this
+        // update would re-enter that other update and potentially recompute them indefinitely, so this update is refused
+        if let Some(&pending) = order.iter().find(|id| self.unsettled.contains(id)) {
+            let cycle = sources
+                .iter()
+                .find_map(|&source| self.path(source, pending))
+                .unwrap_or_default();
+            return Err(Error::Cycle(cycle.into_iter().map(VariableId).collect()));
+        }
+
+        // similarly, the affected variables can't be recomputed while their values are being accessed on this thread (eg: if
+        // the update is made from within the callback of a read of one of them)- accesses on other threads are simply
+        // waited on when the variables are recomputed
+        if order.iter().any(|&id| self.borrowed(id)) {
+            return Err(Error::Reentrancy);
+        }
+
+        Ok(order)
+    }
+
+    /// prepare to propagate an update of the given source variables, returning the order in which the affected variables
+    /// should be recomputed
+    fn plan(&mut self, sources: &[Id]) -> Result<Vec<Id>, Error> {
+        let order = self.order(sources)?;
+        self.local_mut().propagating.extend(order.iter().copied());
+        self.unsettled.extend(order.iter().copied());
+        Ok(order)
+    }
+
+    /// abandon a planned propagation that won't take place after all
+    fn unplan(&mut self, order: &[Id]) {
+        let propagating = &mut self.local_mut().propagating;
+        for id in order {
+            propagating.remove(id);
+        }
+        self.release();
+        self.conclude(order);
+    }
+
+    /// finish a propagation once the `Updated` events of the variables it affected have been emitted
+    fn conclude(&mut self, order: &[Id]) {
+        for id in order {
+            self.unsettled.remove(id);
+        }
+    }
+
+    /// recompute every variable downstream of the given source variables in the planned order, returning the variables that
+    /// were recomputed in the order in which they were recomputed
+    fn propagate(this: System<'x>, sources: &[Id], order: &[Id]) -> Vec<Id> {
+        // only the direct dependents of the sources are known to be stale up-front- the remaining dependents only become stale
+        // once one of the variables they depend on has actually been recomputed
+        let start = {
+            let mut inner = this.hold();
+            for &source in sources {
+                let dependents = inner.dependents(source);
+                inner.local_mut().stale.extend(dependents);
+            }
+            inner.local_mut().recomputed.len()
+        };
+
+        for &dependent in order {
+            // the dependent may already have been dealt with, if a recipe came to read it in the meantime
+            let pending = this.view().pending(dependent);
+            if pending {
+                SystemInner::advance(this.clone(), dependent);
+            }
+        }
+
+        // any updates propagated from within this one have already taken their share of the recomputed variables
+        let mut inner = this.hold();
+        let local = inner.local_mut();
+        let recomputed = local.recomputed.split_off(start);
+        // variables that only came to depend on a recomputed variable after having been dealt with (eg: by reading it once
+        // they were caught up) aren't awaiting recomputation anymore
+        let propagating = &local.propagating;
+        local.stale.retain(|id| propagating.contains(id));
+        inner.release();
+        recomputed
+    }
+
+    /// deal with the given variable as part of the update currently being propagated, recomputing it if it's stale
+    fn advance(mut this: System<'x>, dependent: Id) {
+        // the dependent may have been deleted (or stopped, if it's an effect) by a handler in the meantime
+        let (exists, effect, stale) = {
+            let mut inner = this.hold();
+            let stale = inner.local_mut().stale.remove(&dependent);
+            (inner.recipes.contains_key(&dependent), inner.effects.contains_key(&dependent), stale)
+        };
+
+        if effect && stale {
+            // effects are only re-run once the update has been fully propagated
+            this.hold().schedule(dependent);
+        } else if exists && stale {
+            let lazy = this.hold().lazy.contains(&dependent);
+            if lazy {
+                // lazy variables are merely marked as dirty, to be recomputed the next time they're read- since that
+                // may change their value, their dependents are considered stale too
+                {
+                    let mut inner = this.hold();
+                    inner.dirty.insert(dependent);
+                    let dependents = inner.dependents(dependent);
+                    inner.local_mut().stale.extend(dependents);
+                }
+
+                // nothing is recomputed yet, but any waits on the variable are woken so they can read it
+                _ = this.emit(VariableId(dependent), &Dirtied);
+            } else if proceed(this.emit(VariableId(dependent), &Updating)).is_ok() {
+                // the `Updating` event for the dependent variables can be cancelled as usual, in which case the dependent
+                // keeps its current value and so its own dependents aren't affected by it
+
+                // since we don't have access to the type of the dependent variables, we have to manually recompute them
+                // instead of being able to use the `update` function
+                // if the recomputation didn't actually change the dependent's value, it goes no further
+                if SystemInner::recompute(&this, dependent) {
+                    // the dependents of a recomputed variable are now stale too
+                    let mut inner = this.hold();
+                    let dependents = inner.dependents(dependent);
+                    let local = inner.local_mut();
+                    local.stale.extend(dependents);
+                    local.recomputed.push(dependent);
+                }
+            }
+        }
+
+        this.hold().local_mut().propagating.remove(&dependent);
+    }
+
+    /// bring the given variable up-to-date with the update currently being propagated, if it's still awaiting
+    /// recomputation- the propagation order is planned according to the dependencies from before the update, so a recipe
+    /// may come to read a variable that would otherwise only be recomputed after it
+    fn catch_up(this: &System<'x>, id: Id) {
+        if !this.view().pending(id) {
+            return;
+        }
+
+        // whether the variable is stale depends on whether any of the variables it depends on are recomputed first
+        let sources = this.view().sources.get(&id).cloned().unwrap_or_default();
+        for source in sources {
+            SystemInner::catch_up(this, source);
+        }
+
+        let stale = this.view().local().map_or(false, |local| local.stale.contains(&id));
+        if stale {
+            SystemInner::advance(this.clone(), id);
+        }
+    }
+
+    /// recompute the given lazy variable if it's dirty
+    fn refresh(mut this: System<'x>, id: Id) {
+        // reads are far more common than recomputations, so the variable is only claimed once it's known to be dirty
+        let dirty = this.view().dirty.contains(&id);
+        if !dirty || !this.hold().dirty.remove(&id) {
+            return;
+        }
+
+        // a lazy variable is recomputed just like any other dependent variable, only later- by now its dependents have
+        // already been dealt with, either by being marked as dirty or by having read it as part of their own recomputation
+        if proceed(this.emit(VariableId(id), &Updating)).is_ok() && SystemInner::recompute(&this, id) {
+            // we don't care if this `Updated` event is cancelled as there are no subsequent actions to take
+            _ = this.emit(VariableId(id), &Updated);
+        }
+    }
+
+    /// re-run the recipe of the given variable, returning whether its value changed
+    fn recompute(this: &System<'x>, id: Id) -> bool {
+        let recipe = this
+            .hold()
+            .recipes
+            .get(&id)
+            .cloned()
+            .expect("all variables have a recipe");
+
+        // the recipe may read a different set of variables this time around (eg: if it branches on the value of some variable),
+        // so its previous dependencies are discarded and only the reads made during this run are tracked
+        this.hold().untrack(id);
+        // similarly, any variables created by the previous run are superseded by those created during this run, as is any
+        // asynchronous computation started by the previous run- except for the variables backing projected reads, which
+        // this run reuses
+        this.hold().reserve(id);
+        SystemInner::dispose(this.clone(), id);
+        SystemInner::abandon(this, id);
+
+        // the variable keeps its slot while its recipe runs, so that it's never found missing- the previous value is instead
+        // set aside with the slot claimed, so that accesses on other threads wait for the recomputed value, whereas those on
+        // this thread are refused
+        let slot = match this.view().values.get(&id).cloned() {
+            Some(slot) => slot,
+            // the variable has been deleted in the meantime (eg: on another thread)
+            None => return false,
+        };
+        slot.open();
+        let changed = {
+            let mut guard = claim(&slot.value);
+            let prev = mem::replace(&mut *guard, Box::new(()));
+            this.hold().enter(Some(id));
+            let (value, changed) = recipe(this, Some(prev));
+            // the value is stored before the recipe is considered to have finished, so that whoever is waiting for it to
+            // finish (ie: on another thread) never finds the variable without its value
+            *guard = value;
+            this.hold().exit();
+            changed
+        };
+        slot.close();
+        SystemInner::discard(this.clone(), id);
+        changed
     }
-}
 
-impl<'x> SystemInner<'x> {
-    /// add a new variable to the reactive system
-    fn create<T, F>(mut this: System<'x>, recipe: F) -> Result<Variable<T>, ()>
+    /// register a new effect and run it for the first time
+    fn effect<F>(this: System<'x>, effect: F) -> Effect
     where
-        T: VariableBounds,
-        F: Fn(&System<'x>, Option<T>) -> T + FnBounds + 'x,
+        F: Fn(&mut System<'x>) + FnBounds + 'x,
     {
-        // previously-deleted IDs are reused if possible, otherwise new IDs are allocated by incrementing a global counter
-        let id = {
-            // use an intermediate variable to avoid deadlock
-            let reusable_id = this.hold().id_pool.pop();
-            reusable_id.unwrap_or_else(|| {
-                let id = this.hold().next_id;
-                this.hold().next_id += 1;
-                id
-            })
-        };
+        let id = this.hold().variable_ids.allocate();
+        this.hold().effects.insert(id, Rc::new(effect));
+        SystemInner::run(this, id);
+        Effect(id)
+    }
 
-        // ensure the new variable's dependencies, if any, are tracked
-        this.hold().tracking_id = Some(id);
-        let value = recipe(&this, None);
-        this.hold().tracking_id = None;
+    /// remove an effect from the reactive system
+    fn stop(this: System<'x>, effect: Effect) -> Option<()> {
+        let Effect(id) = effect;
+        this.hold().effects.remove(&id)?;
 
-        let event = Creating { value };
-        // since the variable is not yet created, it's impossible to listen for its local events at this point, so
-        // the `Creating` event is only emitted in the global scope
-        if this
-            .emit(None, &event)
-            .map(|votes| votes.cancel > votes.proceed)
-            .unwrap_or(true)
         {
-            // since the `Creating` event has been cancelled, the ID we selected hasn't ended up being used, so we free it
-            this.hold().id_pool.push(id);
-            return Err(());
+            let mut inner = this.hold();
+            inner.untrack(id);
+            inner.disown(id);
+            inner.selectors.remove(&id);
+            for (_, local) in &mut inner.locals {
+                local.scheduled.retain(|&scheduled| scheduled != id);
+            }
+            inner.variable_ids.free(id);
         }
+        SystemInner::dispose(this, id);
 
-        // reclaim the newly-created value after having temporarily loaned it to the `Creating` event
-        let value = event.value;
-        // store the type-erased initial value
-        this.hold().values.insert(id, Box::new(value));
-        // we have to wrap the recipe somewhat, in order to supply the previous value of the variable as an argument to it
-        // and to type-erase its return value
-        this.hold().recipes.insert(
-            id,
-            Rc::new(move |s| {
-                let prev = s
-                    .hold()
-                    .values
-                    .remove(&id)
-                    .and_then(|v| v.downcast().ok())
-                    .map(|v| *v);
-                Box::new(recipe(s, prev))
-            }),
-        );
+        Some(())
+    }
 
-        let variable = Variable {
-            id,
-            _t: PhantomData,
+    /// run the given effect, tracking the variables it reads
+    fn run(mut this: System<'x>, id: Id) {
+        let effect = match this.hold().effects.get(&id).cloned() {
+            Some(effect) => effect,
+            // the effect may have been stopped after being scheduled
+            None => return,
         };
 
-        // same as the `Creating` event, the `Created` event is emitted only in the global scope as it's impossible to
-        // listen for it locally ahead of time
-        // we don't care if the `Created` event is cancelled, as it doesn't prevent any subsequent actions
-        _ = this.emit(None, &Created { source: variable });
+        // as with recipes, only the reads made during this run are tracked and any variables created by the previous run
+        // are superseded by those created during this run, other than those backing projected reads
+        this.hold().untrack(id);
+        this.hold().reserve(id);
+        SystemInner::dispose(this.clone(), id);
 
-        Ok(variable)
+        this.hold().enter(Some(id));
+        effect(&mut this);
+        this.hold().exit();
+        SystemInner::discard(this.clone(), id);
+
+        // if the effect stopped itself, the reads it made after doing so mustn't be tracked
+        if !this.hold().effects.contains_key(&id) {
+            this.hold().untrack(id);
+        }
     }
 
-    /// read the value of a variable in the reactive system
-    fn read<T, F, S>(
-        mut this: System<'x>,
-        variable: Variable<T>,
-        callback: F,
-    ) -> Result<Option<S>, ()>
-    where
-        T: VariableBounds,
-        F: FnOnce(&T) -> S,
-    {
-        if !this.hold().values.contains_key(&variable.id) {
-            // the target variable doesn't exist so we ignore this request
-            return Ok(None);
+    /// schedule the given effect to be re-run once the updates currently being propagated have been fully propagated
+    fn schedule(&mut self, id: Id) {
+        // an effect that's currently running has already observed the update, as it's the cause of it
+        if self.running(id) {
+            return;
         }
+        let scheduled = &mut self.local_mut().scheduled;
+        if !scheduled.contains(&id) {
+            scheduled.push(id);
+        }
+    }
 
-        // the `Reading` event is cancellable
-        if this
-            .emit(variable, &Reading)
-            .map(|votes| votes.cancel > votes.proceed)
-            .unwrap_or(true)
-        {
-            return Err(());
+    /// re-run the effects scheduled on the current thread, unless updates are still being propagated on it
+    fn flush(this: System<'x>) {
+        loop {
+            let id = {
+                let mut inner = this.hold();
+                let batching = inner.batching();
+                let local = inner.local_mut();
+                // the outermost update or batch will re-run the scheduled effects once it's done propagating
+                if !local.propagating.is_empty() || batching || local.scheduled.is_empty() {
+                    inner.release();
+                    return;
+                }
+                local.scheduled.remove(0)
+            };
+            SystemInner::run(this.clone(), id);
         }
+    }
 
-        // store the tracking ID serparately to avoid deadlock
-        let dependent = this.hold().tracking_id;
-        if let Some(dependent) = dependent {
-            // this variable is being read as part of a new variable's recipe, so we track the dependency
-            // in order to trigger updates when the new variable is changed, and to prevent dangling references
-            this.hold()
-                .dependencies
-                .entry(variable.id)
-                .or_default()
-                .insert(dependent);
+    /// the state specific to the current thread, if it's acting on the system
+    fn local(&self) -> Option<&Local> {
+        let thread = current_thread();
+        self.locals.iter().find(|(id, _)| *id == thread).map(|(_, local)| local)
+    }
+
+    /// the state specific to the current thread
+    fn local_mut(&mut self) -> &mut Local {
+        let thread = current_thread();
+        let index = match self.locals.iter().position(|(id, _)| *id == thread) {
+            Some(index) => index,
+            None => {
+                self.locals.push((thread, Local::default()));
+                self.locals.len() - 1
+            }
+        };
+        &mut self.locals[index].1
+    }
+
+    /// forget the state specific to the current thread once it's done acting on the system
+    fn release(&mut self) {
+        let thread = current_thread();
+        self.locals.retain(|(id, local)| *id != thread || !local.idle());
+    }
+
+    /// the variable whose recipe is running on the current thread, if its tracking scope is the innermost one
+    fn scope(&self) -> Option<Id> {
+        self.local()?.tracking.last().copied().flatten()
+    }
+
+    /// whether a recipe (or effect) is running on the current thread, even if its tracking scope isn't the innermost one
+    fn computing(&self) -> bool {
+        self.local().map_or(false, |local| local.tracking.iter().any(Option::is_some))
+    }
+
+    /// enter the given tracking scope on the current thread
+    fn enter(&mut self, scope: Option<Id>) {
+        self.local_mut().tracking.push(scope);
+    }
+
+    /// exit the innermost tracking scope on the current thread
+    fn exit(&mut self) {
+        self.local_mut().tracking.pop();
+        self.release();
+    }
+
+    /// whether any batches are in progress on the current thread
+    fn batching(&self) -> bool {
+        self.local().map_or(false, |local| local.batch_depth > 0)
+    }
+
+    /// defer the propagation of an update of the given variable until the current thread's batches are committed
+    fn defer(&mut self, id: Id) {
+        let batched = &mut self.local_mut().batched;
+        if !batched.contains(&id) {
+            batched.push(id);
         }
+    }
 
-        // compute the result of the passed callback
-        let ret = callback(
-            // this type system should prevent downcasting errors here, so `unwrap` is used here to preserve the semantic meaning of
-            // an `Ok(Some)`, `Ok(None)`, or `Err` return value from this function
-            this.hold()
-                .values
-                .get(&variable.id)
-                .unwrap()
-                .downcast_ref()
-                .unwrap(),
-        );
+    /// whether the recipe of the given variable (or the given effect) is running on any thread
+    fn running(&self, id: Id) -> bool {
+        self.locals.iter().any(|(_, local)| local.tracking.contains(&Some(id)))
+    }
 
-        // we don't care if `Read` events are cancelled as there are no subsequent actions to take
-        _ = this.emit(variable, &Read);
+    /// whether the recipe of the given variable (or the given effect) is running on the current thread
+    fn entered(&self, id: Id) -> bool {
+        self.local().map_or(false, |local| local.tracking.contains(&Some(id)))
+    }
 
-        Ok(Some(ret))
+    /// wait until the recipe of the given variable (or the given effect) is no longer running on any other thread
+    fn wait(this: &System<'x>, id: Id) {
+        loop {
+            let elsewhere = {
+                let inner = this.view();
+                let thread = current_thread();
+                inner
+                    .locals
+                    .iter()
+                    .any(|(other, local)| *other != thread && local.tracking.contains(&Some(id)))
+            };
+            if !elsewhere {
+                return;
+            }
+            core::hint::spin_loop();
+        }
     }
 
-    /// update the value of a variable in the reactive system
-    fn update<T, F, S>(
-        mut this: System<'x>,
-        variable: Variable<T>,
-        callback: F,
-    ) -> Result<Option<S>, ()>
-    where
-        T: VariableBounds,
-        F: FnOnce(&mut T) -> S,
-    {
-        if !this.hold().values.contains_key(&variable.id) {
-            // the target variable doesn't exist so we ignore this request
-            return Ok(None);
+    /// whether the given variable is still awaiting recomputation as part of the updates the current thread is propagating
+    fn pending(&self, id: Id) -> bool {
+        self.local().map_or(false, |local| local.propagating.contains(&id))
+    }
+
+    /// whether the given variable is in the middle of being recomputed or accessed on the current thread
+    fn reentrant(&self, id: Id) -> bool {
+        self.entered(id) || self.borrowed(id)
+    }
+
+    /// whether the given variable is in the middle of being recomputed or accessed on the current thread, or is awaiting
+    /// recomputation on any thread
+    fn busy(&self, id: Id) -> bool {
+        self.locals.iter().any(|(_, local)| local.propagating.contains(&id)) || self.reentrant(id)
+    }
+
+    /// whether the value of the given variable is currently being accessed on the current thread
+    fn borrowed(&self, id: Id) -> bool {
+        self.values.get(&id).map_or(false, |slot| slot.accessed())
+    }
+
+    /// store the given value as the value of the given variable, in a slot of its own
+    fn store(&mut self, id: Id, value: Value) {
+        self.values.insert(id, Rc::new(Slot::new(value)));
+    }
+
+    /// remove the value of the given variable from the system, unless it's currently being accessed on any thread
+    fn take(&mut self, id: Id) -> Result<Option<Value>, Error> {
+        // values are only removed once nothing is accessing them, in which case nothing else refers to their slots- whoever
+        // is accessing a value holds a reference to its slot for the duration
+        if self.values.get(&id).map_or(false, |slot| Rc::strong_count(slot) > 1) {
+            return Err(Error::Reentrancy);
         }
+        Ok(self.values.remove(&id).and_then(|slot| Rc::try_unwrap(slot).ok()).map(|slot| slot.value.into_inner()))
+    }
 
-        // the `Updating` event is cancellable
-        if this
-            .emit(variable, &Updating)
-            .map(|votes| votes.cancel > votes.proceed)
-            .unwrap_or(true)
-        {
-            return Err(());
+    /// remove the value of the given variable from the system, waiting until it's no longer being accessed
+    fn reclaim(this: &System<'x>, id: Id) -> Option<Value> {
+        loop {
+            // the system isn't held while waiting, so that whoever is accessing the value (ie: a read on another thread)
+            // can finish doing so
+            let value = this.hold().take(id);
+            match value {
+                Ok(value) => return value,
+                Err(_) => core::hint::spin_loop(),
+            }
         }
+    }
 
-        // invoke the callback that will update the variable
-        let ret = callback(
-            // this type system should prevent downcasting errors here, so `unwrap` is used here to preserve the semantic meaning of
-            // an `Ok(Some)`, `Ok(None)`, or `Err` return value from this function
-            this.hold()
-                .values
-                .get_mut(&variable.id)
-                .unwrap()
-                .downcast_mut()
-                .unwrap(),
-        );
+    /// the variables that directly depend on the given variable
+    fn dependents(&self, id: Id) -> BTreeSet<Id> {
+        self.dependencies.get(&id).cloned().unwrap_or_default()
+    }
 
-        // store this variable's dependents (if any) separately to avoid deadlock
-        let dependents = this
-            .hold()
-            .dependencies
-            .get(&variable.id)
-            .cloned()
-            .unwrap_or_default();
-        // we must recompute the values of any variables that depend on the just-changed variable
-        // since we don't have access to the type of the dependent variables, we have to manually recompute them instead of
-        // being able to use the `update` function
-        for dependent in dependents {
-            // the `Updating` event for the dependent variables can be cancelled as usual
-            if this
-                .emit(VariableId(dependent), &Updating)
-                .map(|votes| votes.cancel > votes.proceed)
-                .unwrap_or(true)
-            {
+    /// record that the `dependent` variable depends on the `source` variable
+    fn track(&mut self, source: Id, dependent: Id) {
+        self.dependencies.entry(source).or_default().insert(dependent);
+        self.sources.entry(dependent).or_default().insert(source);
+    }
+
+    /// set aside the hidden variables backing the projected reads made by the previous run of the given variable's recipe (or
+    /// effect), so that they aren't disposed of along with the other variables it owns
+    fn reserve(&mut self, id: Id) {
+        let selectors = match self.selectors.remove(&id) {
+            Some(selectors) => selectors,
+            None => return,
+        };
+        if let Some(owned) = self.owned.get_mut(&id) {
+            for (_, selector) in &selectors {
+                owned.remove(selector);
+            }
+        }
+        self.spare.insert(id, selectors);
+    }
+
+    /// forget the owner of the given variable, if any
+    fn disown(&mut self, id: Id) {
+        if let Some(owner) = self.owners.remove(&id) {
+            if let Some(owned) = self.owned.get_mut(&owner) {
+                owned.remove(&id);
+            }
+        }
+    }
+
+    /// forget all of the variables that the given variable depends on
+    fn untrack(&mut self, dependent: Id) {
+        for source in self.sources.remove(&dependent).into_iter().flatten() {
+            if let Some(dependents) = self.dependencies.get_mut(&source) {
+                dependents.remove(&dependent);
+            }
+        }
+    }
+
+    /// find a chain of dependencies leading from the `source` variable to the `target` variable, if any
+    fn path(&self, source: Id, target: Id) -> Option<Vec<Id>> {
+        // a breadth-first search is used so that the shortest chain is found, with each visited variable remembering the
+        // variable it was reached from so that the chain can be reconstructed
+        let mut parents = BTreeMap::new();
+        let mut queue = alloc::collections::VecDeque::from([source]);
+        while let Some(id) = queue.pop_front() {
+            if id == target {
+                let mut path = alloc::vec![id];
+                let mut id = id;
+                while let Some(&parent) = parents.get(&id) {
+                    path.push(parent);
+                    id = parent;
+                }
+                path.reverse();
+                return Some(path);
+            }
+            for &dependent in self.dependencies.get(&id).into_iter().flatten() {
+                if dependent != source && !parents.contains_key(&dependent) {
+                    parents.insert(dependent, id);
+                    queue.push_back(dependent);
+                }
+            }
+        }
+        None
+    }
+
+    /// order the given source variables and their transitive dependents such that every variable comes after all of the
+    /// variables it depends on
+    fn topological_order(&self, sources: &[Id]) -> Vec<Id> {
+        let mut visited = BTreeSet::new();
+        let mut order = Vec::new();
+
+        // an iterative depth-first search is used so that deep dependency chains can't overflow the stack- each entry is
+        // marked with whether its dependents have already been pushed, at which point it can be placed in the ordering
+        let mut stack = sources.iter().rev().map(|&source| (source, false)).collect::<Vec<_>>();
+        while let Some((id, expanded)) = stack.pop() {
+            if expanded {
+                order.push(id);
+                continue;
+            }
+            if !visited.insert(id) {
                 continue;
             }
+            stack.push((id, true));
+            for &dependent in self.dependencies.get(&id).into_iter().flatten() {
+                if !visited.contains(&dependent) {
+                    stack.push((dependent, false));
+                }
+            }
+        }
+
+        // the search yields the variables in post-order, ie: dependents before the variables they depend on
+        order.reverse();
+        order
+    }
+
+    /// perform several actions, deferring the propagation of any updates until they've all been performed
+    fn batch<R, F>(mut this: System<'x>, f: F) -> Result<R, Error>
+    where
+        F: FnOnce(&mut System<'x>) -> R,
+    {
+        this.hold().local_mut().batch_depth += 1;
+        let ret = f(&mut this);
+        SystemInner::commit(this).map(|_| ret)
+    }
+
+    /// perform several updates as a batch, undoing all of them if any of them fail
+    fn transaction<R, F>(mut this: System<'x>, f: F) -> Result<R, Error>
+    where
+        F: FnOnce(&mut Transaction<'_, 'x>) -> Result<R, Error>,
+    {
+        this.hold().local_mut().batch_depth += 1;
+        // remember which variables were updated before this transaction, so that they're still propagated by any enclosing
+        // batch even if this transaction is rolled back
+        let batched = this.hold().local_mut().batched.clone();
+
+        let mut transaction = Transaction::new(&mut this);
+        let ret = f(&mut transaction);
+        let (journal, error) = transaction.finish();
+
+        // the transaction fails if any of its updates failed, even if the failure wasn't passed on
+        let ret = match error {
+            Some(err) => ret.and(Err(err)),
+            None => ret,
+        };
+        if ret.is_err() {
+            // the previous values of the updated variables are restored directly, since the updates haven't been propagated
+            // yet there's nothing else to undo
+            let writes = {
+                let mut inner = this.hold();
+                // the updates that weren't recorded (eg: those made directly through the system) aren't undone, so they
+                // still need to be propagated
+                inner
+                    .local_mut()
+                    .batched
+                    .retain(|id| batched.contains(id) || !journal.contains_key(id));
+                let mut writes = Vec::new();
+                for (id, value) in journal {
+                    if !inner.values.contains_key(&id) {
+                        continue;
+                    }
+                    // writable variables are never updated directly, but by way of the updates their setters make- so
+                    // their previous values are written back through their setters instead
+                    match inner.setters.get(&id).cloned() {
+                        Some(writer) => writes.push((writer, value)),
+                        None => inner.store(id, value),
+                    }
+                }
+                writes
+            };
+            for (writer, value) in writes {
+                // as with any other write, the setter's reads aren't tracked- and as with the rest of the rollback, the
+                // write is made on a best-effort basis
+                this.hold().enter(None);
+                _ = (writer.set)(&mut this, value);
+                this.hold().exit();
+            }
+        }
+
+        let committed = SystemInner::commit(this);
+        ret.and_then(|ret| committed.map(|_| ret))
+    }
+
+    /// end a batch, propagating its updates if it's the outermost batch
+    fn commit(mut this: System<'x>) -> Result<(), Error> {
+        let sources = {
+            let mut inner = this.hold();
+            let local = inner.local_mut();
+            local.batch_depth -= 1;
+            if local.batch_depth > 0 {
+                // an enclosing batch will propagate the updates once it's committed
+                return Ok(());
+            }
+            let batched = core::mem::take(&mut local.batched);
+            inner.release();
+            batched
+                .into_iter()
+                .filter(|id| inner.values.contains_key(id))
+                .collect::<Vec<_>>()
+        };
 
-            // update the value of the dependent variable
-            let recipe = this
-                .hold()
-                .recipes
-                .get(&dependent)
-                .cloned()
-                .expect("all variables have a recipe");
-            let value = recipe(&this);
-            this.hold().values.insert(dependent, value);
+        // each variable affected by the batch is recomputed at most once, no matter how many of the variables it depends
+        // on were updated
+        let order = this.hold().plan(&sources)?;
+        let recomputed = SystemInner::propagate(this.clone(), &sources, &order);
 
-            // we don't care if this `Updated` event is cancelled as there are no subsequent actions to take for this dependent variable
+        // a single `Updated` event is emitted for each affected variable, no matter how many times it was updated
+        // we don't care if these `Updated` events are cancelled as there are no subsequent actions to take
+        for &dependent in &recomputed {
             _ = this.emit(VariableId(dependent), &Updated);
         }
+        // as with updates, each updated variable is settled before its own `Updated` event is emitted, unless it was also
+        // recomputed as a dependent of another updated variable
+        for source in sources {
+            if !recomputed.contains(&source) {
+                this.hold().conclude(&[source]);
+                _ = this.emit(VariableId(source), &Updated);
+            }
+        }
+        this.hold().conclude(&order);
 
-        // we don't care if this `Updated` event is cancelled as there are no subsequent actions to take
-        _ = this.emit(variable, &Updated);
+        SystemInner::flush(this);
 
-        Ok(Some(ret))
+        Ok(())
+    }
+
+    /// take a copy of the current value of the given variable
+    pub(crate) fn snapshot<T>(this: &System<'x>, id: Id) -> Result<Option<Value>, Error>
+    where
+        T: VariableBounds + Clone,
+    {
+        SystemInner::access(this, id, |value: &T| Box::new(value.clone()) as Value)
     }
 
     /// remove a variable from the reactive system
-    fn delete<T>(mut this: System<'x>, variable: Variable<T>) -> Result<Option<T>, ()>
+    fn delete<T>(this: System<'x>, variable: Variable<T>, policy: Policy) -> Result<Option<T>, Error>
     where
         T: VariableBounds,
     {
-        if !this.hold().values.contains_key(&variable.id) {
+        match SystemInner::remove(this, variable.id, policy)? {
+            // the type system should prevent downcasting errors here, however they're still reported rather than panicking
+            Some(value) => value
+                .downcast()
+                .map(|value| Some(*value))
+                .map_err(|_| Error::TypeMismatch),
+            None => Ok(None),
+        }
+    }
+
+    /// remove a variable from the reactive system without knowledge of its type, returning its type-erased value
+    fn remove(mut this: System<'x>, id: Id, policy: Policy) -> Result<Option<Value>, Error> {
+        if !this.view().values.contains_key(&id) {
             // the target variable doesn't exist so we ignore this request
             return Ok(None);
         }
 
-        // cancel the deletion if the value of any other variables depends on this one, as
-        // that would otherwise leave a dangling reference
-        if this
-            .hold()
-            .dependencies
-            .get(&variable.id)
-            .map(|deps| !deps.is_empty())
-            .unwrap_or_default()
-        {
-            return Err(());
+        // when cascading, the variable is deleted along with every variable that (transitively) depends on it- effects on the
+        // other hand simply stop depending on it
+        let doomed = match policy {
+            Policy::Cascade => {
+                let inner = this.hold();
+                inner
+                    .topological_order(&[id])
+                    .into_iter()
+                    .filter(|id| !inner.effects.contains_key(id))
+                    .collect()
+            }
+            Policy::Refuse | Policy::Orphan => alloc::vec![id],
+        };
+
+        // a variable can't be deleted while it's being recomputed, as the recomputation would otherwise resurrect it- a
+        // recomputation on another thread is simply waited on
+        if doomed.iter().any(|&id| this.view().reentrant(id)) {
+            return Err(Error::Reentrancy);
+        }
+        for &id in &doomed {
+            SystemInner::wait(&this, id);
+        }
+        if doomed.iter().any(|&id| this.hold().busy(id)) {
+            return Err(Error::Reentrancy);
         }
 
-        // the `Deleting` event is cancellable
-        if this
-            .emit(variable, &Deleting)
-            .map(|votes| votes.cancel > votes.proceed)
-            .unwrap_or(true)
-        {
-            return Err(());
+        // cancel the deletion if the value of any other variables depends on this one, as that would otherwise leave a
+        // dangling reference
+        if policy == Policy::Refuse {
+            this.hold().check(&doomed)?;
         }
 
-        // wipe the resources associated with the deleted variable
-        this.hold().dependencies.remove(&variable.id);
-        this.hold().dependencies.values_mut().for_each(|deps| {
-            deps.remove(&variable.id);
-        });
-        this.hold().recipes.remove(&variable.id);
-        this.hold().listeners.values_mut().for_each(|listeners| {
-            listeners.remove(&Some(variable.id));
-        });
-        this.hold().id_pool.push(variable.id);
+        // when orphaning, the variables that depend on this one are detached from it- effects on the other hand simply stop
+        // depending on it
+        let orphans = match policy {
+            Policy::Orphan => {
+                let inner = this.hold();
+                inner
+                    .dependents(id)
+                    .into_iter()
+                    .filter(|dependent| !inner.effects.contains_key(dependent))
+                    .collect()
+            }
+            Policy::Refuse | Policy::Cascade => Vec::new(),
+        };
+        if orphans.iter().any(|&orphan| this.view().reentrant(orphan)) {
+            return Err(Error::Reentrancy);
+        }
+        for &orphan in &orphans {
+            SystemInner::wait(&this, orphan);
+        }
+        if orphans.iter().any(|&orphan| this.hold().busy(orphan)) {
+            return Err(Error::Reentrancy);
+        }
 
-        // this type system should prevent downcasting errors here, so `unwrap` is used here to preserve the semantic meaning of
-        // an `Ok(Some)`, `Ok(None)`, or `Err` return value from this function
-        let value = *this
-            .hold()
-            .values
-            .remove(&variable.id)
-            .unwrap()
-            .downcast()
-            .unwrap();
+        // the `Detaching` and `Deleting` events are cancellable- the deletion is cancelled as a whole if the detaching or
+        // deletion of any of the affected variables is cancelled, with dependents being asked before the variables they depend on
+        for &orphan in &orphans {
+            proceed(this.emit(VariableId(orphan), &Detaching))?;
+        }
+        for &id in doomed.iter().rev() {
+            proceed(this.emit(VariableId(id), &Deleting))?;
+        }
 
-        // we don't care if `Read` events are cancelled as there are no subsequent actions to take
-        _ = this.emit(None, &Deleted { _source: variable });
+        // the handlers may have made other variables depend on the variables being deleted in the meantime
+        match policy {
+            Policy::Refuse | Policy::Cascade => this.hold().check(&doomed)?,
+            Policy::Orphan => {
+                let dependents = this.hold().dependents(id);
+                for dependent in dependents {
+                    SystemInner::freeze(this.clone(), dependent);
+                }
+            }
+        }
+        for orphan in orphans {
+            // we don't care if `Detached` events are cancelled as there are no subsequent actions to take
+            _ = this.emit(VariableId(orphan), &Detached);
+        }
+
+        // dependents are deleted before the variables they depend on, so that no dangling references are left at any point
+        let mut value = None;
+        for doomed in doomed.into_iter().rev() {
+            let removed = SystemInner::wipe(this.clone(), doomed);
+            if doomed == id {
+                value = removed;
+            }
+        }
+
+        Ok(value)
+    }
+
+    /// refuse to delete the given variables if any other variables depend on them
+    fn check(&self, doomed: &[Id]) -> Result<(), Error> {
+        // the hidden variables backing projected reads are an implementation detail, so the variables (or effects) that made
+        // the reads are held responsible for them
+        let dependents = doomed
+            .iter()
+            .flat_map(|&id| self.dependents(id))
+            .filter(|dependent| !doomed.contains(dependent))
+            .map(|dependent| self.principal(dependent))
+            .filter(|dependent| !doomed.contains(dependent) && !self.effects.contains_key(dependent))
+            .collect::<BTreeSet<_>>();
+        if dependents.is_empty() {
+            Ok(())
+        } else {
+            Err(Error::HasDependents(
+                dependents.into_iter().map(VariableId).collect(),
+            ))
+        }
+    }
+
+    /// the variable (or effect) that made the projected read backed by the given hidden variable, or the given variable
+    /// itself if it doesn't back a projected read
+    fn principal(&self, id: Id) -> Id {
+        let owner = match self.owners.get(&id) {
+            Some(&owner) => owner,
+            None => return id,
+        };
+        // the hidden variables are set aside while their owner's recipe (or effect) re-runs
+        let selector = self
+            .selectors
+            .get(&owner)
+            .into_iter()
+            .chain(self.spare.get(&owner))
+            .flatten()
+            .any(|&(_, selector)| selector == id);
+        if selector {
+            owner
+        } else {
+            id
+        }
+    }
+
+    /// turn the given derived variable into a plain variable that holds its most recent value
+    fn freeze(this: System<'x>, id: Id) {
+        if this.hold().effects.contains_key(&id) {
+            // effects aren't variables, so there's nothing to freeze
+            return;
+        }
+
+        // the most recent value of a lazy variable may not have been computed yet, whereas the result of an asynchronous
+        // computation that's still in progress would no longer be wanted
+        SystemInner::refresh(this.clone(), id);
+        SystemInner::abandon(&this, id);
+
+        let mut inner = this.hold();
+        inner.untrack(id);
+        inner.lazy.remove(&id);
+        // writes to a plain variable are applied directly
+        inner.setters.remove(&id);
+        // the recipe of a plain variable simply keeps its current value- this way the variable can't come to depend on any
+        // other variables again
+        inner.recipes.insert(
+            id,
+            Rc::new(|_, prev| (prev.expect("recipes only run for existing variables"), false)),
+        );
+    }
+
+    /// wipe the resources associated with the given variable, returning its type-erased value
+    fn wipe(mut this: System<'x>, id: Id) -> Option<Value> {
+        // any remaining dependents are effects, which simply stop depending on the deleted variable
+        let dependents = this.hold().dependents(id);
+        for dependent in dependents {
+            if let Some(sources) = this.hold().sources.get_mut(&dependent) {
+                sources.remove(&id);
+            }
+        }
+        this.hold().dependencies.remove(&id);
+        this.hold().untrack(id);
+        this.hold().disown(id);
+        this.hold().selectors.remove(&id);
+        SystemInner::abandon(&this, id);
+        this.hold().recipes.remove(&id);
+        this.hold().lazy.remove(&id);
+        this.hold().dirty.remove(&id);
+        this.hold().cutoffs.remove(&id);
+        this.hold().setters.remove(&id);
+        // the removed handlers are only dropped once the system is no longer held, as dropping them may have side-effects
+        let listeners = write(&this.view().listeners)
+            .values_mut()
+            .filter_map(|targets| targets.remove(&Some(id)))
+            .collect::<Vec<_>>();
+        for ListenerId(listener) in listeners.into_iter().flat_map(IndexMap::into_keys) {
+            this.hold().listener_ids.free(listener);
+        }
+        this.hold().variable_ids.free(id);
+
+        let value = SystemInner::reclaim(&this, id);
+        let deleter = this.hold().deleters.remove(&id);
+        if let Some(deleter) = deleter {
+            deleter(&mut this, id);
+        }
+
+        // the variables owned by the deleted variable are deleted along with it
+        SystemInner::dispose(this, id);
 
-        Ok(Some(value))
+        value
+    }
+
+    /// delete the hidden variables set aside for the latest run of the given variable's recipe (or effect) that it didn't reuse
+    fn discard(this: System<'x>, owner: Id) {
+        let spare = this.hold().spare.remove(&owner).unwrap_or_default();
+        for (_, selector) in spare {
+            // as with owned variables, they're deleted on a best-effort basis
+            _ = SystemInner::remove(this.clone(), selector, Policy::Refuse);
+        }
+    }
+
+    /// delete every variable owned by the given variable
+    fn dispose(this: System<'x>, owner: Id) {
+        let owned = this.hold().owned.remove(&owner).unwrap_or_default();
+        for id in owned {
+            this.hold().owners.remove(&id);
+            // owned variables are deleted on a best-effort basis- if the deletion is cancelled, or if other variables have
+            // come to depend on an owned variable, it simply outlives its owner
+            _ = SystemInner::remove(this.clone(), id, Policy::Refuse);
+        }
     }
 
     /// register a function to be called
@@ -568,7 +3240,7 @@ impl<'x> SystemInner<'x> {
         let target_id = target.into().map(|VariableId(id)| id);
 
         if target_id
-            .map(|id| !this.hold().values.contains_key(&id))
+            .map(|id| !this.view().values.contains_key(&id))
             .unwrap_or_default()
         {
             // the target variable doesn't exist so we ignore this request
@@ -585,19 +3257,10 @@ impl<'x> SystemInner<'x> {
             },
         );
 
-        // listener IDs are allocated from the same pool as variables
-        let id = {
-            let reusable_id = this.hold().id_pool.pop();
-            reusable_id.unwrap_or_else(|| {
-                let id = this.hold().next_id;
-                this.hold().next_id += 1;
-                id
-            })
-        };
+        let id = ListenerId(this.hold().listener_ids.allocate());
 
         // store the event handler
-        this.hold()
-            .listeners
+        write(&this.view().listeners)
             .entry(TypeId::of::<E>())
             .or_default()
             .entry(target_id)
@@ -613,38 +3276,49 @@ impl<'x> SystemInner<'x> {
 
     /// trigger an event, optionally on a given target
     fn emit<E>(
-        mut this: System<'x>,
+        this: System<'x>,
         target: impl Into<Option<VariableId>>,
         event: &E,
-    ) -> Result<Votes, ()>
+    ) -> Result<Votes, Error>
     where
         E: 'static,
     {
         // extract the ID of the passed target, if any
         let target_id = target.into().map(|VariableId(id)| id);
 
+        Self::dispatch(this, TypeId::of::<E>(), target_id, event)
+    }
+
+    /// triggers the handlers for a type-erased event
+    fn dispatch(
+        mut this: System<'x>,
+        event_type: TypeId,
+        target_id: Option<Id>,
+        event: &dyn Any,
+    ) -> Result<Votes, Error> {
         // gather the relevant handlers for this event & target
-        let handlers = this
-            .hold()
-            .listeners
-            .get(&event.type_id())
+        let handlers = read(&this.view().listeners)
+            .get(&event_type)
             .and_then(|targets| targets.get(&target_id))
             .into_iter()
             .flatten()
-            .map(|(_, handler)| handler)
-            .cloned()
+            .map(|(&id, handler)| (id, handler.clone()))
             .collect::<Vec<_>>();
 
         let mut votes = Votes::default();
-        for handler in handlers {
+        for (id, handler) in handlers {
             // by default this handler will proceed without affecting subsequent ones
             let mut vote = Vote::Abstain;
             let mut abort = false;
+            // handlers aren't part of any recipe, so the reads they make aren't tracked as dependencies of whichever variable's
+            // recipe may have triggered this event
+            this.hold().enter(None);
             handler(&mut this, event, &mut vote, &mut abort);
+            this.hold().exit();
 
             // if aborted, subsqeuent handlers are skipped
             if abort {
-                return Err(());
+                return Err(Error::Aborted(id));
             }
 
             // votes are tallied following the execution of alll handlers, so we continue on
@@ -658,16 +3332,68 @@ impl<'x> SystemInner<'x> {
         Ok(votes)
     }
 
+    /// adds the events sent from other threads to the posted events
+    #[cfg(feature = "std")]
+    fn receive(&self) {
+        if let Some(inbox) = &self.inbox {
+            let inbox = lock(inbox);
+            lock(&self.posted).extend(inbox.receiver.try_iter().map(|(target_id, event)| (target_id, event as Message)));
+        }
+    }
+
+    /// triggers the oldest posted event, unless posted events are already being triggered
+    fn run_one(this: System<'x>) -> Option<Result<Votes, Error>> {
+        let (target_id, event) = {
+            let mut inner = this.hold();
+            if inner.local().map_or(false, |local| local.draining) {
+                return None;
+            }
+            #[cfg(feature = "std")]
+            inner.receive();
+            let posted = lock(&inner.posted).pop_front()?;
+            inner.local_mut().draining = true;
+            posted
+        };
+
+        // `*event` is the boxed event itself, rather than the box
+        let result = Self::dispatch(this.clone(), (*event).type_id(), target_id, &*event);
+        {
+            let mut inner = this.hold();
+            inner.local_mut().draining = false;
+            inner.release();
+        }
+
+        Some(result)
+    }
+
     /// removes an event listener
     fn silence<E>(this: System<'x>, listener: Listener<E>) -> Option<()>
     where
         E: 'static,
     {
-        this.hold()
+        let mut inner = this.hold();
+        let handler = inner
             .listeners
+            .get_mut()
             .get_mut(&TypeId::of::<E>())
             .and_then(|targets| targets.get_mut(&listener.target))
-            .and_then(|handlers| handlers.shift_remove(&listener.id))
-            .map(|_| ())
+            .and_then(|handlers| handlers.shift_remove(&listener.id))?;
+        // the listener's ID can be reused now that it's been removed
+        inner.listener_ids.free(listener.id.0);
+        // the handler is only dropped once the system is no longer held, as dropping it may have side-effects
+        drop(inner);
+        drop(handler);
+        Some(())
+    }
+}
+
+/// determine whether the effects of a built-in event should be followed through, ie: whether the event wasn't aborted and
+/// the votes to proceed >= the votes to cancel
+fn proceed(result: Result<Votes, Error>) -> Result<(), Error> {
+    let votes = result?;
+    if votes.cancel > votes.proceed {
+        Err(Error::Cancelled(votes))
+    } else {
+        Ok(())
     }
 }
@@ -0,0 +1,138 @@
+use crate::{
+    compat::{lock, BoxFuture, Cell, Rc, VariableBounds},
+    system::{System, SystemInner},
+    variable::Variable,
+};
+
+use alloc::boxed::Box;
+use core::{
+    future::Future,
+    pin::Pin,
+    ptr,
+    task::{Context, Poll, RawWaker, RawWakerVTable, Waker},
+};
+
+/// The state of a variable whose value is computed asynchronously
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum AsyncState<T, E> {
+    /// The computation is still in progress
+    Pending,
+    /// The computation succeeded with the given value
+    Ready(T),
+    /// The computation failed with the given error
+    Failed(E),
+}
+
+impl<T, E> From<Result<T, E>> for AsyncState<T, E> {
+    fn from(result: Result<T, E>) -> Self {
+        match result {
+            Ok(value) => Self::Ready(value),
+            Err(err) => Self::Failed(err),
+        }
+    }
+}
+
+/// An asynchronous computation that has yet to complete, which should be driven to completion by an executor.\
+/// Once the computation completes, its result is stored in its variable- if the variable is recomputed (or deleted) in the
+/// meantime, the computation is cancelled and the task completes without polling it any further.
+pub struct Task<'x>(pub(crate) BoxFuture<'x>);
+
+impl Future for Task<'_> {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.0.as_mut().poll(cx)
+    }
+}
+
+/// the state shared between a computation in flight and the system
+#[derive(Default)]
+pub(crate) struct Flight {
+    cancelled: bool,
+    /// the waker of the task driving the computation, so that it can be woken to complete once cancelled
+    waker: Option<Waker>,
+}
+
+impl Flight {
+    /// cancel the given computation
+    pub(crate) fn cancel(flight: &Cell<Flight>) {
+        let waker = {
+            let mut flight = lock(flight);
+            flight.cancelled = true;
+            flight.waker.take()
+        };
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+    }
+}
+
+/// drives a computation to completion after it has been polled for the first time, storing its result in its variable
+pub(crate) struct InFlight<'x, T, E, Fut>
+where
+    T: VariableBounds,
+    E: VariableBounds,
+{
+    pub(crate) system: System<'x>,
+    pub(crate) variable: Variable<AsyncState<T, E>>,
+    pub(crate) future: Pin<Box<Fut>>,
+    pub(crate) flight: Rc<Cell<Flight>>,
+    /// the result of the computation, if it has completed but its result couldn't be stored yet
+    pub(crate) settled: Option<AsyncState<T, E>>,
+}
+
+// the computation is boxed, and none of the other fields are ever pinned
+impl<T, E, Fut> Unpin for InFlight<'_, T, E, Fut>
+where
+    T: VariableBounds,
+    E: VariableBounds,
+{
+}
+
+impl<'x, T, E, Fut> Future for InFlight<'x, T, E, Fut>
+where
+    T: VariableBounds,
+    E: VariableBounds,
+    Fut: Future<Output = Result<T, E>>,
+{
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+
+        {
+            let mut flight = lock(&this.flight);
+            if flight.cancelled {
+                return Poll::Ready(());
+            }
+            flight.waker = Some(cx.waker().clone());
+        }
+
+        let state = match this.settled.take() {
+            Some(state) => state,
+            None => match this.future.as_mut().poll(cx) {
+                Poll::Ready(result) => result.into(),
+                Poll::Pending => return Poll::Pending,
+            },
+        };
+
+        match SystemInner::settle(this.system.clone(), this.variable, &this.flight, state) {
+            Ok(()) => Poll::Ready(()),
+            // the result couldn't be stored just yet, so the task is woken to try again
+            Err(state) => {
+                this.settled = Some(state);
+                cx.waker().wake_by_ref();
+                Poll::Pending
+            }
+        }
+    }
+}
+
+/// a waker that does nothing, for polling a computation for the first time outside of any executor
+pub(crate) fn noop_waker() -> Waker {
+    const VTABLE: RawWakerVTable = RawWakerVTable::new(|_| RAW, |_| {}, |_| {}, |_| {});
+    const RAW: RawWaker = RawWaker::new(ptr::null(), &VTABLE);
+
+    // SAFETY: none of the vtable's functions access the (null) data pointer
+    unsafe { Waker::from_raw(RAW) }
+}
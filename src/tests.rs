@@ -0,0 +1,721 @@
+use crate::{
+    compat::{lock, Cell, Rc},
+    events::{Created, Creating, Reading, Updated, Updating},
+    task::noop_waker,
+    AsyncState, Error, ReactiveMap, ReactiveVec, System, Task, Variable, VariableId, Vote,
+};
+
+use alloc::{string::String, vec, vec::Vec};
+use core::{
+    future::{self, Future},
+    mem,
+    pin::Pin,
+    sync::atomic::{AtomicBool, AtomicUsize, Ordering},
+    task::{Context, Poll},
+};
+
+#[test]
+fn reads_forming_a_cycle_are_refused() {
+    let mut system = System::default();
+
+    let target = system.create(|_, _| None::<Variable<i32>>).unwrap();
+    let b = system
+        .create(move |s, _| match s.read(target, |v| *v).unwrap().unwrap() {
+            Some(target) => match s.read(target, |v| *v) {
+                Err(Error::Cycle(_)) => -1,
+                other => other.unwrap().unwrap(),
+            },
+            None => 1,
+        })
+        .unwrap();
+    let c = system.create(move |s, _| s.read(b, |v| *v * 2).unwrap().unwrap()).unwrap();
+
+    // `c` depends on `b`, so `b` can't depend on `c`
+    _ = system.update(target, |v| *v = Some(c));
+    assert_eq!(Ok(Some(-1)), system.read(b, |v| *v));
+    assert_eq!(Ok(Some(-2)), system.read(c, |v| *v));
+}
+
+#[test]
+fn variables_can_be_read_but_not_updated_while_being_read() {
+    let mut system = System::default();
+
+    let a = system.create(|_, _| 10).unwrap();
+    assert_eq!(Ok(Some(20)), system.read(a, |v| *v + system.read(a, |v| *v).unwrap().unwrap()));
+
+    let mut handle = system.clone();
+    assert_eq!(Ok(Some(Err(Error::Reentrancy))), system.read(a, |_| handle.update(a, |v| *v += 1)));
+    assert_eq!(Ok(Some(10)), system.read(a, |v| *v));
+}
+
+#[test]
+fn variables_can_be_updated_but_not_accessed_while_being_updated() {
+    let mut system = System::default();
+
+    let a = system.create(|_, _| 1).unwrap();
+    let b = system.create(|_, _| 10).unwrap();
+
+    let handle = system.clone();
+    let ret = system.update(a, |v| {
+        *v += handle.read(b, |v| *v).unwrap().unwrap();
+        handle.read(a, |v| *v)
+    });
+    assert_eq!(Ok(Some(Err(Error::Reentrancy))), ret);
+    assert_eq!(Ok(Some(11)), system.read(a, |v| *v));
+}
+
+#[test]
+fn variables_deleted_before_being_read_are_missing() {
+    let mut system = System::default();
+
+    let a = system.create(|_, _| 0).unwrap();
+    system.listen(a, move |s, _: &Reading, _, _| {
+        _ = s.delete(a);
+    });
+    assert_eq!(Ok(None), system.read(a, |v| *v));
+}
+
+#[test]
+fn writable_variables_keep_their_value_while_being_written() {
+    let mut system = System::default();
+
+    let a = system.create(|_, _| 1).unwrap();
+    let target = system.create(|_, _| None::<Variable<i32>>).unwrap();
+    let seen = system.create(|_, _| None).unwrap();
+    let b = system
+        .create_writable(
+            move |s, _| s.read(a, |v| *v * 2).unwrap().unwrap(),
+            move |s, v| {
+                // the setter reads the variable being written
+                let b = s.read(target, |v| *v)?.flatten().unwrap();
+                let current = s.read(b, |v| *v)?;
+                s.update(seen, |seen| *seen = current)?;
+                s.update(a, |a| *a = v / 2).map(|_| ())
+            },
+        )
+        .unwrap();
+    _ = system.update(target, |v| *v = Some(b));
+
+    assert_eq!(Ok(Some(())), system.update(b, |v| *v = 10));
+    assert_eq!(Ok(Some(Some(2))), system.read(seen, |v| *v));
+    assert_eq!(Ok(Some(10)), system.read(b, |v| *v));
+}
+
+/// a writable variable in celsius, backed by a plain variable in fahrenheit
+fn thermometer(system: &mut System<'static>) -> (Variable<f64>, Variable<f64>) {
+    let fahrenheit = system.create(|_, _| 212.0).unwrap();
+    let celsius = system
+        .create_writable(
+            move |s, _| s.read(fahrenheit, |v| (*v - 32.0) * 5.0 / 9.0).unwrap().unwrap(),
+            move |s, celsius| s.update(fahrenheit, |v| *v = celsius * 9.0 / 5.0 + 32.0).map(|_| ()),
+        )
+        .unwrap();
+    (fahrenheit, celsius)
+}
+
+#[test]
+fn writable_variables_are_only_updated_once_per_batch() {
+    let mut system = System::default();
+    let (fahrenheit, celsius) = thermometer(&mut system);
+
+    let updates = system.create(|_, _| 0).unwrap();
+    system.listen(celsius, move |s, _: &Updated, _, _| {
+        _ = s.update(updates, |v| *v += 1);
+    });
+    let ret = system.batch(|s| {
+        _ = s.update(celsius, |v| *v = 0.0);
+        s.read(fahrenheit, |v| *v)
+    });
+    assert_eq!(Ok(Ok(Some(32.0))), ret);
+    assert_eq!(Ok(Some(1)), system.read(updates, |v| *v));
+}
+
+#[test]
+fn writable_variables_fail_to_update_if_their_setters_fail() {
+    let mut system = System::default();
+    let (fahrenheit, celsius) = thermometer(&mut system);
+
+    system.listen(fahrenheit, |_, _: &Updating, vote, _| *vote = Vote::Cancel);
+    assert!(matches!(system.update(celsius, |v| *v = 0.0), Err(Error::Cancelled(_))));
+    assert_eq!(Ok(Some(100.0)), system.read(celsius, |v| *v));
+}
+
+#[test]
+fn writable_variables_cannot_be_updated_while_being_read() {
+    let mut system = System::default();
+    let (_, celsius) = thermometer(&mut system);
+
+    let mut handle = system.clone();
+    let ret = system.read(celsius, |_| handle.update(celsius, |v| *v = 50.0));
+    assert_eq!(Ok(Some(Err(Error::Reentrancy))), ret);
+    assert_eq!(Ok(Some(100.0)), system.read(celsius, |v| *v));
+}
+
+#[test]
+fn rolled_back_writes_to_writable_variables_are_undone() {
+    let mut system = System::default();
+    let (fahrenheit, celsius) = thermometer(&mut system);
+
+    let cancelled = system.create(|_, _| 0).unwrap();
+    system.listen(cancelled, |_, _: &Updating, vote, _| *vote = Vote::Cancel);
+
+    let ret = system.transaction(|tx| {
+        tx.update(celsius, |v| *v = 0.0)?;
+        tx.update(cancelled, |v| *v += 1)?;
+        Ok(())
+    });
+    assert!(matches!(ret, Err(Error::Cancelled(_))));
+    assert_eq!(Ok(Some(212.0)), system.read(fahrenheit, |v| *v));
+    assert_eq!(Ok(Some(100.0)), system.read(celsius, |v| *v));
+}
+
+#[test]
+fn dependencies_are_retracked_whenever_a_recipe_reruns() {
+    let mut system = System::default();
+
+    let flag = system.create(|_, _| true).unwrap();
+    let x = system.create(|_, _| 1).unwrap();
+    let y = system.create(|_, _| 2).unwrap();
+    let z = system
+        .create(move |s, _| {
+            let flag = s.read(flag, |v| *v).unwrap().unwrap();
+            s.read(if flag { x } else { y }, |v| *v).unwrap().unwrap_or_default()
+        })
+        .unwrap();
+    assert_eq!(Ok(Some(1)), system.read(z, |v| *v));
+
+    _ = system.update(flag, |v| *v = false);
+    assert_eq!(Ok(Some(2)), system.read(z, |v| *v));
+
+    // `z` no longer depends on `x`
+    assert_eq!(Ok(Some(1)), system.delete(x));
+}
+
+#[test]
+fn recipes_observe_variables_awaiting_recomputation_up_to_date() {
+    let mut system = System::default();
+
+    let n = system.create(|_, _| 1).unwrap();
+    let pair = system.create(|_, _| (0, 0)).unwrap();
+    let tens = system.create(move |s, _| s.read(n, |v| *v * 10).unwrap().unwrap()).unwrap();
+    // `pair` only comes to depend on `tens` once `n` is updated, so it's planned to be recomputed before `tens`
+    _ = system.set_recipe(pair, move |s, _| {
+        let n = s.read(n, |v| *v).unwrap().unwrap();
+        if n < 2 {
+            return (n, 0);
+        }
+        (n, s.read(tens, |v| *v).unwrap().unwrap())
+    });
+
+    _ = system.update(n, |v| *v = 2);
+    assert_eq!(Ok(Some((2, 20))), system.read(pair, |v| *v));
+}
+
+#[test]
+fn untracked_reads_observe_variables_awaiting_recomputation_up_to_date() {
+    let mut system = System::default();
+
+    let a = system.create(|_, _| 1).unwrap();
+    let peeked = system.create(|_, _| (0, 0)).unwrap();
+    let untracked = system.create(|_, _| (0, 0)).unwrap();
+    let b = system.create(move |s, _| s.read(a, |v| *v * 10).unwrap().unwrap()).unwrap();
+    // neither variable depends on `b`, so they may be planned to be recomputed before it
+    _ = system.set_recipe(peeked, move |s, _| {
+        (s.read(a, |v| *v).unwrap().unwrap(), s.peek(b, |v| *v).unwrap().unwrap())
+    });
+    _ = system.set_recipe(untracked, move |s, _| {
+        let a = s.read(a, |v| *v).unwrap().unwrap();
+        (a, s.untracked(|s| s.read(b, |v| *v)).unwrap().unwrap())
+    });
+
+    _ = system.update(a, |v| *v = 2);
+    assert_eq!(Ok(Some(20)), system.read(b, |v| *v));
+    assert_eq!(Ok(Some((2, 20))), system.read(peeked, |v| *v));
+    assert_eq!(Ok(Some((2, 20))), system.read(untracked, |v| *v));
+}
+
+#[test]
+fn updates_reentering_a_propagation_in_progress_are_refused() {
+    let mut system = System::default();
+
+    let a = system.create(|_, _| 1).unwrap();
+    let b = system.create(move |s, _| s.read(a, |v| *v).unwrap().unwrap()).unwrap();
+
+    let updating = system
+        .listen(b, move |s, _: &Updating, _, _| {
+            assert!(matches!(s.update(a, |v| *v += 1), Err(Error::Cycle(_))));
+        })
+        .unwrap();
+    assert_eq!(Ok(Some(())), system.update(a, |v| *v += 1));
+    assert_eq!(Ok(Some(2)), system.read(b, |v| *v));
+    system.silence(updating);
+
+    // an update is still in progress until every `Updated` event it causes has been emitted
+    system.listen(b, move |s, _: &Updated, _, _| {
+        assert!(matches!(s.update(a, |v| *v += 1), Err(Error::Cycle(_))));
+    });
+    assert_eq!(Ok(Some(())), system.update(a, |v| *v += 1));
+    assert_eq!(Ok(Some(3)), system.read(b, |v| *v));
+}
+
+#[test]
+fn updated_handlers_can_undo_the_update() {
+    let mut system = System::default();
+
+    // as in the `extension` example, the previous value is remembered when the update starts and restored once it's done
+    // if the new value is unwanted
+    let x = system.create(|_, _| 0).unwrap();
+    let prev = system.create(|_, _| 0).unwrap();
+    let undoing = system.create(|_, _| false).unwrap();
+    system.listen(x, move |s, _: &Updating, _, _| {
+        let v = s.read(x, |v| *v).unwrap().unwrap();
+        _ = s.update(prev, |prev| *prev = v);
+    });
+    system.listen(x, move |s, _: &Updated, _, _| {
+        if s.read(undoing, |v| *v).unwrap().unwrap() {
+            _ = s.update(undoing, |v| *v = false);
+        } else if s.read(x, |v| *v).unwrap().unwrap() > 10 {
+            _ = s.update(undoing, |v| *v = true);
+            let prev = s.read(prev, |v| *v).unwrap().unwrap();
+            assert_eq!(Ok(Some(())), s.update(x, |v| *v = prev));
+        }
+    });
+
+    assert_eq!(Ok(Some(())), system.update(x, |v| *v = 2));
+    assert_eq!(Ok(Some(())), system.update(x, |v| *v = 6));
+    assert_eq!(Ok(Some(())), system.update(x, |v| *v = 20));
+    assert_eq!(Ok(Some(6)), system.read(x, |v| *v));
+    assert_eq!(Ok(Some(false)), system.read(undoing, |v| *v));
+}
+
+#[test]
+fn projected_reads_are_unaffected_by_global_events() {
+    static CREATED: AtomicUsize = AtomicUsize::new(0);
+
+    let mut system = System::default();
+
+    let a = system.create(|_, _| (String::from("hi"), 0)).unwrap();
+    let len = system.create(move |s, _| s.read_projected(a, |v| v.0.clone()).unwrap().unwrap().len()).unwrap();
+
+    system.listen(None, |_, _: &Creating<Option<String>>, vote, _| *vote = Vote::Cancel);
+    system.listen(None, |_, _: &Created<Option<String>>, _, _| {
+        CREATED.fetch_add(1, Ordering::Relaxed);
+    });
+    for _ in 0..10 {
+        _ = system.update(a, |v| v.0.push('!'));
+    }
+    assert_eq!(Ok(Some(12)), system.read(len, |v| *v));
+    assert_eq!(0, CREATED.load(Ordering::Relaxed));
+}
+
+#[test]
+fn projected_reads_are_attributed_to_the_variables_making_them() {
+    let mut system = System::default();
+
+    let a = system.create(|_, _| (1, 2)).unwrap();
+    let b = system.create(move |s, _| s.read_projected(a, |v| v.0).unwrap()).unwrap();
+    assert_eq!(Err(Error::HasDependents(vec![b.into()])), system.delete(a));
+
+    // effects simply stop depending on deleted variables, including through projected reads
+    _ = system.delete(b);
+    let effect = system.effect(move |s| _ = s.read_projected(a, |v| v.1));
+    assert_eq!(Ok(Some((1, 2))), system.delete(a));
+    system.stop(effect);
+}
+
+#[test]
+fn async_results_completing_while_being_read_are_stored_once_the_read_has_finished() {
+    let mut system = System::default();
+
+    let open = Rc::new(AtomicBool::new(false));
+    let tasks = Rc::new(Cell::new(Vec::<Task>::new()));
+    let run = {
+        let tasks = tasks.clone();
+        move || {
+            let waker = noop_waker();
+            let mut cx = Context::from_waker(&waker);
+            let pending = mem::take(&mut *lock(&tasks));
+            for mut task in pending {
+                if Pin::new(&mut task).poll(&mut cx).is_pending() {
+                    lock(&tasks).push(task);
+                }
+            }
+        }
+    };
+    let output = {
+        let (open, tasks) = (open.clone(), tasks.clone());
+        system
+            .create_async(
+                move |_| {
+                    let open = open.clone();
+                    async move {
+                        future::poll_fn(|_| if open.load(Ordering::SeqCst) { Poll::Ready(()) } else { Poll::Pending }).await;
+                        Ok::<_, ()>(10)
+                    }
+                },
+                move |task| lock(&tasks).push(task),
+            )
+            .unwrap()
+    };
+
+    open.store(true, Ordering::SeqCst);
+    _ = system.read(output, |_| run());
+    assert_eq!(Ok(Some(AsyncState::Pending)), system.read(output, |v| *v));
+    run();
+    assert_eq!(Ok(Some(AsyncState::Ready(10))), system.read(output, |v| *v));
+    assert!(lock(&tasks).is_empty());
+}
+
+#[test]
+fn collections_are_unaffected_by_listeners_on_their_elements_type() {
+    let mut system = System::default();
+
+    _ = system.listen(None, |_, _: &Creating<Option<i32>>, vote, _| *vote = Vote::Cancel);
+
+    let vec = ReactiveVec::new(&mut system).unwrap();
+    assert_eq!(Ok(()), vec.push(&mut system, 1));
+    assert_eq!(Ok(vec![1]), vec.to_vec(&system));
+
+    let map = ReactiveMap::new(&mut system).unwrap();
+    assert_eq!(Ok(None), map.insert(&mut system, "a", 1));
+    assert_eq!(Ok(Some(1)), map.get(&system, &"a", |v| *v));
+}
+
+#[test]
+fn failed_insertions_discard_the_slots_they_created() {
+    let mut system = System::default();
+
+    let vec = ReactiveVec::new(&mut system).unwrap();
+    vec.push(&mut system, 1).unwrap();
+    let map = ReactiveMap::new(&mut system).unwrap();
+    map.insert(&mut system, "a", 1).unwrap();
+
+    // the slot created by each failed insertion recycles the ID freed by the previous one, so `marker`'s ID is recycled
+    // ahead of `bound`'s as long as none of them are leaked
+    let marker = system.create(|_, _| ()).unwrap();
+    let bound = system.create(|_, _| ()).unwrap();
+    _ = system.delete(marker);
+
+    let cancel = |_: &mut System, _: &Updating, vote: &mut Vote, _: &mut bool| *vote = Vote::Cancel;
+    let listeners = [system.listen(vec, cancel).unwrap(), system.listen(map, cancel).unwrap()];
+    for n in 0..3 {
+        assert!(vec.push(&mut system, n).is_err());
+        assert!(map.insert(&mut system, "b", n).is_err());
+    }
+    for listener in listeners {
+        system.silence(listener);
+    }
+
+    let next = system.create(|_, _| ()).unwrap();
+    assert!(VariableId::from(next) < VariableId::from(bound));
+
+    assert_eq!(Ok(vec![1]), vec.to_vec(&system));
+    assert_eq!(Ok(vec!["a"]), map.keys(&system));
+    assert_eq!(Ok(None), map.get(&system, &"b", |v| *v));
+    assert_eq!(Ok(None), map.insert(&mut system, "b", 2));
+    assert_eq!(Ok(vec!["a", "b"]), map.keys(&system));
+}
+
+#[test]
+fn shifts_failing_part_way_are_undone() {
+    let mut system = System::default();
+
+    let vec = ReactiveVec::new(&mut system).unwrap();
+    for n in 0..5 {
+        vec.push(&mut system, n).unwrap();
+    }
+
+    // the element at index 3 can't be shifted while it's being read, so each shift fails part-way and is undone
+    let mut handle = system.clone();
+    let ret = vec.get(&system, 3, |_| vec.insert(&mut handle, 0, 99));
+    assert_eq!(Ok(Some(Err(Error::Reentrancy))), ret);
+    assert_eq!(Ok(vec![0, 1, 2, 3, 4]), vec.to_vec(&system));
+    assert_eq!(Ok(5), vec.len(&system));
+
+    let ret = vec.get(&system, 3, |_| vec.remove(&mut handle, 0));
+    assert_eq!(Ok(Some(Err(Error::Reentrancy))), ret);
+    assert_eq!(Ok(vec![0, 1, 2, 3, 4]), vec.to_vec(&system));
+    assert_eq!(Ok(5), vec.len(&system));
+}
+
+#[cfg(not(feature = "unsync"))]
+mod threaded {
+    use crate::{events::Updated, Error, System, Variable};
+
+    use std::{
+        sync::{Arc, Barrier},
+        thread::{self, JoinHandle},
+        time::{Duration, Instant},
+        vec::Vec,
+    };
+
+    /// the read of a variable on another thread
+    type Reader = JoinHandle<Result<Option<i32>, Error>>;
+
+    /// a system in which `b` (twice `a`, which is 1) is in the middle of being recomputed on another thread
+    fn recomputing() -> (System<'static>, Variable<i32>, Reader) {
+        let mut system = System::default();
+
+        let started = Arc::new(Barrier::new(2));
+        let a = system.create(|_, _| 0).unwrap();
+        let b = {
+            let started = started.clone();
+            system
+                .create_lazy(move |s, _| {
+                    let a = s.read(a, |v| *v).unwrap().unwrap();
+                    if a > 0 {
+                        started.wait();
+                        thread::sleep(Duration::from_millis(50));
+                    }
+                    a * 2
+                })
+                .unwrap()
+        };
+        _ = system.update(a, |v| *v += 1);
+
+        let reader = {
+            let system = system.clone();
+            thread::spawn(move || system.read(b, |v| *v))
+        };
+        started.wait();
+        (system, b, reader)
+    }
+
+    #[test]
+    fn variables_being_recomputed_on_other_threads_can_be_read() {
+        let (system, b, reader) = recomputing();
+        assert_eq!(Ok(Some(2)), system.read(b, |v| *v));
+        assert_eq!(Ok(Some(2)), reader.join().unwrap());
+    }
+
+    #[test]
+    fn variables_being_recomputed_on_other_threads_can_be_peeked_at() {
+        let (system, b, reader) = recomputing();
+        assert_eq!(Ok(Some(2)), system.peek(b, |v| *v));
+        assert_eq!(Ok(Some(2)), reader.join().unwrap());
+    }
+
+    #[test]
+    fn variables_being_recomputed_on_other_threads_can_be_read_projected() {
+        let (mut system, b, reader) = recomputing();
+        let c = system.create(move |s, _| s.read_projected(b, |v| *v + 1).unwrap()).unwrap();
+        assert_eq!(Ok(Some(Some(3))), system.read(c, |v| *v));
+        assert_eq!(Ok(Some(2)), reader.join().unwrap());
+    }
+
+    #[test]
+    fn variables_being_recomputed_on_other_threads_can_be_listened_to() {
+        let (system, b, reader) = recomputing();
+        assert!(system.listen(b, |_, _: &Updated, _, _| ()).is_some());
+        assert_eq!(Ok(Some(2)), reader.join().unwrap());
+    }
+
+    #[test]
+    fn variables_being_recomputed_on_other_threads_can_be_waited_on() {
+        let (system, b, reader) = recomputing();
+        assert_eq!(Ok(Some(true)), system.wait_until(b, |v| *v == 2, Duration::from_secs(10)));
+        assert_eq!(Ok(Some(2)), reader.join().unwrap());
+    }
+
+    #[test]
+    fn variables_being_recomputed_on_other_threads_can_be_deleted() {
+        let (mut system, b, reader) = recomputing();
+        assert_eq!(Ok(Some(2)), system.delete(b));
+        // the read may or may not have finished before the deletion
+        assert!(reader.join().unwrap().is_ok());
+        assert_eq!(Ok(None), system.read(b, |v| *v));
+    }
+
+    #[test]
+    fn recipes_only_depend_on_their_own_reads() {
+        let mut system = System::default();
+
+        let a = system.create(|_, _| 0).unwrap();
+        let barrier = Arc::new(Barrier::new(2));
+        let reader = {
+            let (system, barrier) = (system.clone(), barrier.clone());
+            thread::spawn(move || {
+                barrier.wait();
+                let ret = system.read(a, |v| *v);
+                barrier.wait();
+                ret
+            })
+        };
+        let b = system
+            .create(move |_, _| {
+                // the other thread reads `a` while this recipe is running
+                barrier.wait();
+                barrier.wait();
+                1
+            })
+            .unwrap();
+
+        assert_eq!(Ok(Some(0)), reader.join().unwrap());
+        assert_eq!(Ok(Some(0)), system.delete(a));
+        assert_eq!(Ok(Some(1)), system.read(b, |v| *v));
+    }
+
+    #[test]
+    fn updates_wait_for_reads_on_other_threads() {
+        let mut system = System::default();
+
+        let a = system.create(|_, _| 0).unwrap();
+        let reading = Arc::new(Barrier::new(2));
+        let reader = {
+            let (system, reading) = (system.clone(), reading.clone());
+            thread::spawn(move || {
+                system.read(a, |v| {
+                    reading.wait();
+                    thread::sleep(Duration::from_millis(10));
+                    *v
+                })
+            })
+        };
+
+        reading.wait();
+        assert_eq!(Ok(Some(())), system.update(a, |v| *v += 1));
+        assert_eq!(Ok(Some(0)), reader.join().unwrap());
+        assert_eq!(Ok(Some(1)), system.read(a, |v| *v));
+    }
+
+    #[test]
+    fn waits_on_lazy_variables_are_woken_when_they_become_out_of_date() {
+        let mut system = System::default();
+
+        let a = system.create(|_, _| 0).unwrap();
+        let b = system.create_lazy(move |s, _| s.read(a, |v| *v * 2).unwrap().unwrap()).unwrap();
+        let waiter = {
+            let system = system.clone();
+            thread::spawn(move || {
+                let start = Instant::now();
+                (system.wait_until(b, |v| *v == 10, Duration::from_secs(10)), start.elapsed())
+            })
+        };
+
+        // nothing reads `b` in the meantime, and the wait is checked once more when it times out, so it has to finish
+        // well before then
+        for _ in 0..5 {
+            thread::sleep(Duration::from_millis(10));
+            assert_eq!(Ok(Some(())), system.update(a, |v| *v += 1));
+        }
+        let (result, elapsed) = waiter.join().unwrap();
+        assert_eq!(Ok(Some(true)), result);
+        assert!(elapsed < Duration::from_secs(5));
+    }
+
+    #[test]
+    fn reentrant_updates_are_refused_rather_than_waiting_on_other_threads() {
+        let mut system = System::default();
+
+        let a = system.create(|_, _| 0).unwrap();
+        let b = system.create(move |s, _| s.read(a, |v| *v + 1).unwrap().unwrap()).unwrap();
+
+        let reading = Arc::new(Barrier::new(2));
+        let reader = {
+            let (system, reading) = (system.clone(), reading.clone());
+            thread::spawn(move || {
+                let mut handle = system.clone();
+                system.read(b, |_| {
+                    reading.wait();
+                    // by now the other thread is recomputing `b`, which waits for this read to finish
+                    thread::sleep(Duration::from_millis(50));
+                    handle.update(b, |v| *v += 1)
+                })
+            })
+        };
+
+        reading.wait();
+        assert_eq!(Ok(Some(())), system.update(a, |v| *v += 1));
+        assert_eq!(Ok(Some(Err(Error::Reentrancy))), reader.join().unwrap());
+        assert_eq!(Ok(Some(2)), system.read(b, |v| *v));
+    }
+
+    #[test]
+    fn detaching_waits_for_recomputations_on_other_threads() {
+        let mut system = System::default();
+
+        let a = system.create(|_, _| 0).unwrap();
+        let b = system
+            .create_lazy(move |s, _| {
+                let a = s.read(a, |v| *v).unwrap().unwrap();
+                if a > 0 {
+                    thread::sleep(Duration::from_millis(50));
+                }
+                a
+            })
+            .unwrap();
+        _ = system.update(a, |v| *v += 1);
+
+        let reader = {
+            let system = system.clone();
+            thread::spawn(move || system.read(b, |v| *v))
+        };
+        // let the other thread start recomputing `b`
+        thread::sleep(Duration::from_millis(10));
+        assert_eq!(Ok(Some(())), system.detach(b));
+        assert_eq!(Ok(Some(1)), reader.join().unwrap());
+
+        // the recomputation didn't make `b` depend on `a` again
+        assert_eq!(Ok(Some(1)), system.delete(a));
+        assert_eq!(Ok(Some(1)), system.read(b, |v| *v));
+    }
+
+    #[test]
+    fn updates_of_disjoint_chains_propagate_in_parallel() {
+        let mut system = System::default();
+
+        let chains = (0..4)
+            .map(|_| {
+                let head = system.create(|_, _| 0).unwrap();
+                let mut tail = head;
+                for _ in 0..4 {
+                    let source = tail;
+                    tail = system.create(move |s, _| s.read(source, |v| *v + 1).unwrap().unwrap()).unwrap();
+                }
+                let updates = system.create(|_, _| 0).unwrap();
+                system.listen(tail, move |s, _: &Updated, _, _| {
+                    _ = s.update(updates, |v| *v += 1);
+                });
+                (head, tail, updates)
+            })
+            .collect::<Vec<_>>();
+
+        let start = Arc::new(Barrier::new(chains.len()));
+        let workers = chains
+            .iter()
+            .map(|&(head, _, _)| {
+                let (mut system, start) = (system.clone(), start.clone());
+                thread::spawn(move || {
+                    start.wait();
+                    for _ in 0..1000 {
+                        assert_eq!(Ok(Some(())), system.update(head, |v| *v += 1));
+                    }
+                })
+            })
+            .collect::<Vec<_>>();
+        for worker in workers {
+            worker.join().unwrap();
+        }
+
+        // each thread only emitted `Updated` for its own chain
+        for (_, tail, updates) in chains {
+            assert_eq!(Ok(Some(1004)), system.read(tail, |v| *v));
+            assert_eq!(Ok(Some(1000)), system.read(updates, |v| *v));
+        }
+    }
+
+    #[test]
+    fn batches_only_defer_their_own_threads_updates() {
+        let mut system = System::default();
+
+        let a = system.create(|_, _| 1).unwrap();
+        let double = system.create(move |s, _| s.read(a, |v| *v * 2).unwrap().unwrap()).unwrap();
+
+        let ret = system.batch(|s| {
+            let mut handle = s.clone();
+            thread::spawn(move || handle.update(a, |v| *v = 10)).join().unwrap()?;
+            s.read(double, |v| *v)
+        });
+        assert_eq!(Ok(Ok(Some(20))), ret);
+    }
+}
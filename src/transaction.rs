@@ -0,0 +1,79 @@
+use crate::{
+    compat::{Value, VariableBounds},
+    error::Error,
+    id::Id,
+    system::{System, SystemInner},
+    variable::Variable,
+};
+
+use alloc::collections::BTreeMap;
+use core::ops::{Deref, DerefMut};
+
+/// A handle to the reactive system that records the updates made through it, so that they can be undone.\
+/// All of the regular system actions are available through this handle, however only the updates made via
+/// [`Transaction::update`] can be undone.
+pub struct Transaction<'a, 'x> {
+    system: &'a mut System<'x>,
+    /// the value each updated variable held before it was first updated as part of this transaction
+    journal: BTreeMap<Id, Value>,
+    /// the first error encountered by any of the updates made as part of this transaction
+    error: Option<Error>,
+}
+
+impl<'a, 'x> Transaction<'a, 'x> {
+    pub(crate) fn new(system: &'a mut System<'x>) -> Self {
+        Self {
+            system,
+            journal: BTreeMap::new(),
+            error: None,
+        }
+    }
+
+    /// Update the value of a variable as part of this transaction.\
+    /// This behaves just like [`System::update`], except that the previous value of the variable is recorded so that the
+    /// update can be undone if the transaction fails- if the update itself fails, the whole transaction fails.\
+    /// The update of a writable variable is undone by writing its previous value back through its setter.
+    ///
+    /// Returns:
+    /// - [`Err`], if the action was cancelled (see [`Error`])
+    /// - an [`Ok`] value containing [`None`], if the target variable doesn't exist
+    /// - an [`Ok`] value containing a [`Some`] value containing the result of the passed callback, otherwise
+    pub fn update<T, F, S>(&mut self, variable: Variable<T>, callback: F) -> Result<Option<S>, Error>
+    where
+        T: VariableBounds + Clone,
+        F: FnOnce(&mut T) -> S,
+    {
+        if !self.journal.contains_key(&variable.id) {
+            // only the value from before the first update needs to be recorded
+            let snapshot = SystemInner::snapshot::<T>(self.system, variable.id);
+            if let Ok(Some(snapshot)) = snapshot {
+                self.journal.insert(variable.id, snapshot);
+            }
+        }
+
+        let ret = self.system.update(variable, callback);
+        if let Err(err) = &ret {
+            self.error.get_or_insert_with(|| err.clone());
+        }
+        ret
+    }
+
+    /// release the recorded previous values, along with the first error encountered
+    pub(crate) fn finish(self) -> (BTreeMap<Id, Value>, Option<Error>) {
+        (self.journal, self.error)
+    }
+}
+
+impl<'x> Deref for Transaction<'_, 'x> {
+    type Target = System<'x>;
+
+    fn deref(&self) -> &Self::Target {
+        self.system
+    }
+}
+
+impl<'x> DerefMut for Transaction<'_, 'x> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.system
+    }
+}
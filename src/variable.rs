@@ -10,9 +10,15 @@ pub struct Variable<T: VariableBounds> {
     pub(crate) _t: PhantomData<T>,
 }
 
-impl<T: VariableBounds> Into<Option<VariableId>> for Variable<T> {
-    fn into(self) -> Option<VariableId> {
-        Some(VariableId(self.id))
+impl<T: VariableBounds> From<Variable<T>> for Option<VariableId> {
+    fn from(variable: Variable<T>) -> Self {
+        Some(VariableId(variable.id))
+    }
+}
+
+impl<T: VariableBounds> From<Variable<T>> for VariableId {
+    fn from(variable: Variable<T>) -> Self {
+        VariableId(variable.id)
     }
 }
 
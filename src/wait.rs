@@ -0,0 +1,74 @@
+use crate::{compat::VariableBounds, error::Error, events::{Dirtied, Updated}, system::System, variable::Variable};
+
+use std::{
+    thread::{self, Thread},
+    time::{Duration, Instant},
+};
+
+/// held by the listener backing a wait, so that the waiting thread is woken whenever the listener is removed (eg: if the
+/// variable being waited on is deleted)
+struct Unpark(Thread);
+
+impl Drop for Unpark {
+    fn drop(&mut self) {
+        self.0.unpark();
+    }
+}
+
+/// block the current thread until the value of the given variable satisfies the given predicate, or until the timeout
+/// elapses- a timeout too large to be represented as a deadline never elapses
+pub(crate) fn wait_until<T, P>(
+    system: &System<'_>,
+    variable: Variable<T>,
+    predicate: P,
+    timeout: Duration,
+) -> Result<Option<bool>, Error>
+where
+    T: VariableBounds,
+    P: Fn(&T) -> bool,
+{
+    let deadline = Instant::now().checked_add(timeout);
+
+    // the listener is registered before the predicate is first checked, so that no update can slip through in between-
+    // an unpark that arrives before the thread is parked simply makes the next park return immediately
+    let unpark = Unpark(thread::current());
+    let listener = match system.listen(variable, move |_, _: &Updated, _, _| unpark.0.unpark()) {
+        Some(listener) => listener,
+        // the target variable doesn't exist
+        None => return Ok(None),
+    };
+    // a lazy variable is only recomputed (and so only emits the `Updated` event) once it's read, so it's read as soon as
+    // it's marked as out-of-date
+    let unpark = Unpark(thread::current());
+    let dirtied = match system.listen(variable, move |_, _: &Dirtied, _, _| unpark.0.unpark()) {
+        Some(listener) => listener,
+        None => {
+            system.clone().silence(listener);
+            return Ok(None);
+        }
+    };
+
+    let result = loop {
+        match system.peek(variable, &predicate) {
+            Ok(Some(false)) => {}
+            // the predicate is satisfied, or the variable has been deleted in the meantime
+            other => break other,
+        }
+
+        // spurious wake-ups are harmless, as the predicate is simply checked again
+        match deadline {
+            Some(deadline) => {
+                let now = Instant::now();
+                if now >= deadline {
+                    break Ok(Some(false));
+                }
+                thread::park_timeout(deadline - now);
+            }
+            None => thread::park(),
+        }
+    };
+
+    system.clone().silence(listener);
+    system.clone().silence(dirtied);
+    result
+}